@@ -0,0 +1,92 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::{
+    Backend, BoxScene, ErasedSceneManager, SceneError, SceneHandle, SceneStack, Transition,
+};
+
+/// A [`SceneStack`] along with a borrowed context, so it can be driven without passing the
+/// context to every call, see [`SceneStack::with_context`].
+///
+/// The context can be any part of a bigger one, which is how a child stack runs over a slice
+/// of its parent's state without a copy of its own: keep a `SceneStack<Audio>` in a scene or
+/// in the parent context, then drive it with `child.with_context(&mut ctx.audio)`.
+pub struct BoundStack<'a, C, E, B: Backend> {
+    stack: &'a mut SceneStack<C, E, B>,
+    ctx: &'a mut C,
+}
+
+impl<'a, C, E: From<SceneError>, B: Backend> BoundStack<'a, C, E, B> {
+    pub(crate) fn new(stack: &'a mut SceneStack<C, E, B>, ctx: &'a mut C) -> Self {
+        Self { stack, ctx }
+    }
+
+    pub fn ctx(&mut self) -> &mut C {
+        self.ctx
+    }
+
+    /// See [`SceneStack::on_create_borrowed`].
+    pub fn create(&mut self) -> Result<bool, E> {
+        self.stack.on_create_borrowed(self.ctx)
+    }
+
+    /// See [`SceneStack::on_update_borrowed`].
+    pub fn update(&mut self, elapsed_time: f64) -> Result<bool, E> {
+        self.stack.on_update_borrowed(self.ctx, elapsed_time)
+    }
+
+    /// See [`SceneStack::on_event_borrowed`].
+    pub fn event(&mut self, event: &B::Event) -> Result<bool, E> {
+        self.stack.on_event_borrowed(self.ctx, event)
+    }
+
+    /// See [`SceneStack::apply`].
+    pub fn apply(&mut self, trans: Transition<C, E, B>) -> Result<(), E> {
+        self.stack.apply(self.ctx, trans)
+    }
+
+    /// See [`SceneStack::push`].
+    pub fn push(&mut self, new: BoxScene<C, E, B>) -> Result<SceneHandle, E> {
+        self.stack.push(self.ctx, new)
+    }
+
+    /// See [`SceneStack::pop`].
+    pub fn pop(&mut self) -> Result<Option<BoxScene<C, E, B>>, E> {
+        self.stack.pop(self.ctx)
+    }
+}
+
+impl<C, E, B: Backend> Deref for BoundStack<'_, C, E, B> {
+    type Target = SceneStack<C, E, B>;
+
+    fn deref(&self) -> &Self::Target {
+        self.stack
+    }
+}
+
+impl<C, E, B: Backend> DerefMut for BoundStack<'_, C, E, B> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.stack
+    }
+}
+
+impl<C, E: From<SceneError>, B: Backend> ErasedSceneManager<E, B> for BoundStack<'_, C, E, B> {
+    fn create(&mut self) -> Result<bool, E> {
+        BoundStack::create(self)
+    }
+
+    fn update(&mut self, elapsed_time: f64) -> Result<bool, E> {
+        BoundStack::update(self, elapsed_time)
+    }
+
+    fn event(&mut self, event: &B::Event) -> Result<bool, E> {
+        BoundStack::event(self, event)
+    }
+
+    fn len(&self) -> usize {
+        self.stack.len()
+    }
+
+    fn current_name(&self) -> Option<&str> {
+        self.stack.current_name()
+    }
+}
@@ -0,0 +1,50 @@
+use crate::{Backend, BoxError, SceneError, SceneManager, SgeBackend};
+
+/// A [`SceneManager`] with its context type hidden, so managers with different contexts can
+/// be stored together, E.G. as `Box<dyn ErasedSceneManager>` in a plugin host that can't name
+/// each plugin's context.
+///
+/// Only the error and backend types are left, as they're part of the methods' signatures.
+pub trait ErasedSceneManager<E = BoxError, B: Backend = SgeBackend> {
+    /// See [`crate::SceneStack::on_create_borrowed`].
+    fn create(&mut self) -> Result<bool, E>;
+
+    /// Run one frame, see [`crate::SceneStack::on_update_borrowed`].
+    fn update(&mut self, elapsed_time: f64) -> Result<bool, E>;
+
+    /// See [`crate::SceneStack::on_event_borrowed`].
+    fn event(&mut self, event: &B::Event) -> Result<bool, E>;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn current_name(&self) -> Option<&str>;
+}
+
+impl<C, E: From<SceneError>, B: Backend> ErasedSceneManager<E, B> for SceneManager<C, E, B> {
+    fn create(&mut self) -> Result<bool, E> {
+        let (stack, ctx) = self.split();
+        stack.on_create_borrowed(ctx)
+    }
+
+    fn update(&mut self, elapsed_time: f64) -> Result<bool, E> {
+        let (stack, ctx) = self.split();
+        stack.on_update_borrowed(ctx, elapsed_time)
+    }
+
+    fn event(&mut self, event: &B::Event) -> Result<bool, E> {
+        let (stack, ctx) = self.split();
+        stack.on_event_borrowed(ctx, event)
+    }
+
+    fn len(&self) -> usize {
+        (**self).len()
+    }
+
+    fn current_name(&self) -> Option<&str> {
+        (**self).current_name()
+    }
+}
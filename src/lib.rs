@@ -1,26 +1,505 @@
-use std::error::Error;
+use std::{
+    any::Any,
+    cell::RefCell,
+    collections::{HashMap, VecDeque},
+    error::Error,
+    fmt,
+    rc::{Rc, Weak},
+    sync::atomic::{AtomicU64, Ordering},
+    task::Poll,
+    time::Instant,
+};
 
-pub trait Scene {
+// Logs through `tracing` when the feature is enabled, and compiles to nothing otherwise
+#[cfg(feature = "tracing")]
+macro_rules! trace {
+    ($($arg:tt)*) => { tracing::trace!($($arg)*) };
+}
+#[cfg(not(feature = "tracing"))]
+macro_rules! trace {
+    ($($arg:tt)*) => {};
+}
+#[cfg(feature = "tracing")]
+macro_rules! warn {
+    ($($arg:tt)*) => { tracing::warn!($($arg)*) };
+}
+#[cfg(not(feature = "tracing"))]
+macro_rules! warn {
+    ($($arg:tt)*) => {};
+}
+
+mod bound_stack;
+mod erased;
+mod pause_menu;
+mod scene_group;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
+mod transition_scene;
+
+pub use bound_stack::BoundStack;
+pub use erased::ErasedSceneManager;
+pub use pause_menu::PauseMenu;
+pub use scene_group::SceneGroup;
+pub use transition_scene::TransitionScene;
+
+/// The error type used by scenes unless they specify their own.
+pub type BoxError = Box<dyn Error>;
+
+/// A scene on the heap, as kept on the stack and carried by transitions.
+pub type BoxScene<C, E = BoxError, B = SgeBackend> = Box<dyn Scene<E, B, Context = C>>;
+
+/// What [`Scene::on_event_flow`] returns.
+pub type EventFlowResult<C, E = BoxError, B = SgeBackend> =
+    Result<(EventFlow, Transition<C, E, B>), E>;
+
+// Shorthands for the boxed callbacks held by transitions and the stack
+type ScenePredicate<C, E, B> = Box<dyn FnMut(&dyn Scene<E, B, Context = C>) -> bool>;
+type SceneContextPredicate<C, E, B> = Box<dyn FnMut(&dyn Scene<E, B, Context = C>, &C) -> bool>;
+type SceneMapper<C, E, B> = Box<dyn FnOnce(BoxScene<C, E, B>) -> BoxScene<C, E, B>>;
+type TransitionObserver<C> = Box<dyn FnMut(&TransitionKind, &mut C)>;
+type CursorCallback<C> = Box<dyn FnMut(CursorMode, &mut C)>;
+type MessageSlot = Rc<RefCell<Option<Box<dyn Any>>>>;
+
+/// Errors raised by the scene manager itself, rather than by scenes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SceneError {
+    /// An operation needed a scene on the stack, but it was empty.
+    EmptyStack,
+    /// A scene index was past the top of the stack.
+    OutOfRange { index: usize, len: usize },
+    /// A transition would have grown the stack past its maximum depth.
+    DepthExceeded { max: usize },
+    /// The scene at this index panicked, and was removed from the stack.
+    ScenePanicked { depth: usize },
+    /// Scenes kept following up from `on_enter_transition` or `on_leave_transition` more times
+    /// in a row than this, probably because two of them lead to each other.
+    RedirectLoop { max: usize },
+}
+
+impl fmt::Display for SceneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyStack => write!(f, "The scene stack is empty"),
+            Self::OutOfRange { index, len } => write!(
+                f,
+                "Scene index {} is out of range for a stack of {} scenes",
+                index, len
+            ),
+            Self::DepthExceeded { max } => {
+                write!(f, "The scene stack can't hold more than {} scenes", max)
+            }
+            Self::ScenePanicked { depth } => write!(f, "The scene at index {} panicked", depth),
+            Self::RedirectLoop { max } => write!(
+                f,
+                "Scenes followed up more than {} times in a row when entered or left",
+                max
+            ),
+        }
+    }
+}
+
+impl Error for SceneError {}
+
+/// Returned when one of several transitions applied together fails.
+#[derive(Debug)]
+pub struct TransitionError<E = BoxError> {
+    /// The position of the failed transition in the batch
+    pub index: usize,
+    /// The error the transition failed with
+    pub source: E,
+}
+
+impl<E: fmt::Display> fmt::Display for TransitionError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Transition {} failed: {}", self.index, self.source)
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> Error for TransitionError<E> {}
+
+/// Allows a scene to be downcast to its concrete type.
+///
+/// This is implemented automatically for every `'static` type, so scenes never need to
+/// implement it themselves. Call it on a `&dyn Scene` (E.G. from [`SceneManager::current`])
+/// rather than on a `Box<dyn Scene>`, otherwise you'll get the box itself back.
+pub trait AsAny {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: Any> AsAny for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// Wrappers around the lifecycle callbacks, so they can all be traced in one place
+impl<C, E, B: Backend> dyn Scene<E, B, Context = C> {
+    fn enter(&mut self, ctx: &mut C, seed: Option<u64>) -> Result<(), E> {
+        self.seed(seed);
+        trace!(scene = self.name(), "on_enter");
+        self.on_enter(ctx)
+    }
+
+    fn enter_with(
+        &mut self,
+        ctx: &mut C,
+        payload: Box<dyn Any>,
+        seed: Option<u64>,
+    ) -> Result<(), E> {
+        self.seed(seed);
+        trace!(scene = self.name(), "on_enter_with");
+        self.on_enter_with(ctx, payload)
+    }
+
+    fn seed(&mut self, seed: Option<u64>) {
+        if let Some(seed) = seed {
+            trace!(scene = self.name(), seed, "on_seed");
+            self.on_seed(seed);
+        }
+    }
+
+    fn before_leave(&mut self, ctx: &mut C) -> Result<bool, E> {
+        trace!(scene = self.name(), "on_before_leave");
+        self.on_before_leave(ctx)
+    }
+
+    fn leave(&mut self, ctx: &mut C) -> Result<(), E> {
+        trace!(scene = self.name(), "on_leave");
+        self.on_leave(ctx)
+    }
+
+    fn pause(&mut self, ctx: &mut C, should_draw: bool, reason: Option<&dyn Any>) -> Result<(), E> {
+        trace!(
+            scene = self.name(),
+            should_draw,
+            has_reason = reason.is_some(),
+            "on_pause"
+        );
+        self.on_pause_with(ctx, should_draw, reason)
+    }
+
+    fn unpause(
+        &mut self,
+        ctx: &mut C,
+        result: Option<Box<dyn Any>>,
+        was_drawn: bool,
+    ) -> Result<(), E> {
+        trace!(
+            scene = self.name(),
+            has_result = result.is_some(),
+            was_drawn,
+            "on_unpause"
+        );
+        self.on_unpause_with(ctx, result, was_drawn)
+    }
+}
+
+/// The engine that scenes run on, so the scene stack isn't tied to `sge`.
+///
+/// Scenes and managers use [`SgeBackend`] unless told otherwise. To drive a [`SceneStack`]
+/// from another engine, implement this and call the stack's `*_borrowed` methods from that
+/// engine's main loop.
+pub trait Backend: 'static {
+    /// The events passed to `Scene::on_event`
+    type Event;
+
+    /// The change to the window that `event` describes, if any. These are passed to every scene
+    /// through `on_resize`, `on_background` and `on_foreground`, before the event is dispatched
+    /// as usual.
+    fn window_change(event: &Self::Event) -> Option<WindowChange>;
+
+    /// Which category `event` falls into, for skipping scenes whose [`Scene::event_mask`]
+    /// excludes it. Everything is [`EventMask::OTHER`] by default.
+    fn event_category(_event: &Self::Event) -> EventMask {
+        EventMask::OTHER
+    }
+}
+
+/// A set of event categories, see [`Scene::event_mask`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EventMask(u8);
+
+impl EventMask {
+    pub const NONE: Self = Self(0);
+    pub const WINDOW: Self = Self(1);
+    pub const KEYBOARD: Self = Self(1 << 1);
+    pub const MOUSE: Self = Self(1 << 2);
+    pub const TEXT: Self = Self(1 << 3);
+    /// Anything not covered by another category
+    pub const OTHER: Self = Self(1 << 4);
+    pub const ALL: Self = Self(0b11111);
+
+    /// Whether this set shares any category with `other`.
+    pub fn intersects(self, other: Self) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    /// Whether this set includes every category in `other`.
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl Default for EventMask {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+impl std::ops::BitOr for EventMask {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitOrAssign for EventMask {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// A change to the window that every scene is told about, see [`Backend::window_change`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum WindowChange {
+    Resized { width: u32, height: u32 },
+    FocusLost,
+    FocusGained,
+}
+
+/// The `sge` engine, which is the default backend.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SgeBackend;
+
+impl Backend for SgeBackend {
+    type Event = sge::Event;
+
+    fn window_change(event: &sge::Event) -> Option<WindowChange> {
+        let sge::Event::Window { win_event, .. } = event else {
+            return None;
+        };
+        match *win_event {
+            sge::WindowEvent::SizeChanged(width, height) => Some(WindowChange::Resized {
+                width: width as u32,
+                height: height as u32,
+            }),
+            sge::WindowEvent::FocusLost => Some(WindowChange::FocusLost),
+            sge::WindowEvent::FocusGained => Some(WindowChange::FocusGained),
+            _ => None,
+        }
+    }
+
+    fn event_category(event: &sge::Event) -> EventMask {
+        match event {
+            sge::Event::Window { .. } => EventMask::WINDOW,
+            sge::Event::KeyDown { .. } | sge::Event::KeyUp { .. } => EventMask::KEYBOARD,
+            sge::Event::MouseMotion { .. }
+            | sge::Event::MouseButtonDown { .. }
+            | sge::Event::MouseButtonUp { .. }
+            | sge::Event::MouseWheel { .. } => EventMask::MOUSE,
+            sge::Event::TextInput { .. } | sge::Event::TextEditing { .. } => EventMask::TEXT,
+            _ => EventMask::OTHER,
+        }
+    }
+}
+
+/// What a scene did with an event, and whether the scenes below it should see it too.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EventFlow {
+    /// The event wasn't handled, pass it on
+    #[default]
+    Ignore,
+    /// The event was handled, but pass it on anyway
+    Continue,
+    /// The event was handled, don't pass it on
+    Consume,
+}
+
+impl EventFlow {
+    pub fn handled(self) -> bool {
+        self != Self::Ignore
+    }
+}
+
+/// What the scenes on the stack did with an event, all together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventOutcome {
+    /// A scene consumed the event, so the scenes below it didn't see it
+    Consumed,
+    /// At least one scene handled the event, but none of them consumed it
+    Observed,
+    /// No scene handled the event
+    Ignored,
+}
+
+impl EventOutcome {
+    pub fn handled(self) -> bool {
+        self != Self::Ignored
+    }
+}
+
+/// How the top scene wants the mouse cursor, see [`Scene::cursor`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum CursorMode {
+    /// Leave the cursor however the scenes before it left it
+    #[default]
+    Inherit,
+    Visible,
+    Hidden,
+    /// Hidden and confined to the window, E.G. for mouse look
+    Locked,
+}
+
+impl From<bool> for EventFlow {
+    fn from(handled: bool) -> Self {
+        if handled {
+            Self::Consume
+        } else {
+            Self::Ignore
+        }
+    }
+}
+
+/// A scene on a [`SceneManager`]'s stack.
+///
+/// `E` is the error type returned by every callback. It defaults to [`BoxError`], but can be
+/// any type that a [`SceneError`] converts into, such as your own error enum.
+pub trait Scene<E = BoxError, B: Backend = SgeBackend>: AsAny {
     type Context;
 
-    fn on_enter(&mut self, _ctx: &mut Self::Context) -> Result<(), Box<dyn Error>> {
+    fn on_enter(&mut self, _ctx: &mut Self::Context) -> Result<(), E> {
         Ok(())
     }
-    fn on_leave(&mut self, _ctx: &mut Self::Context) -> Result<(), Box<dyn Error>> {
+    /// Called instead of `on_enter` when the scene is pushed with [`Transition::PushWith`],
+    /// receiving the data the transition carried. Downcast `payload` to get at it. Falls back to
+    /// `on_enter` by default, ignoring the payload.
+    fn on_enter_with(&mut self, ctx: &mut Self::Context, _payload: Box<dyn Any>) -> Result<(), E> {
+        self.on_enter(ctx)
+    }
+    /// Called just before `on_enter` or `on_enter_with` with a seed for this scene's random
+    /// numbers, when the stack has been given one with [`SceneManager::set_seed`]. Every scene
+    /// gets a different seed, but the same one every run. Does nothing by default.
+    fn on_seed(&mut self, _seed: u64) {}
+
+    /// Called once the scene is on the stack, just after `on_enter` or `on_enter_with`, with a
+    /// handle it can keep to send a message to the scene below it, E.G. for a dialog to tell
+    /// the scene that opened it what was picked as soon as it's picked. Does nothing by
+    /// default.
+    fn on_parent(&mut self, _parent: ParentRef) {}
+
+    /// A transition to apply as soon as this scene has been entered, E.G. for a bootstrap scene
+    /// that jumps straight to the continue screen if there's a save, without waiting for a
+    /// frame. Called once after the transition that entered the scene (or restarted it) has
+    /// been applied, so the stack is consistent by then. A scene brought in by a
+    /// [`Transition::crossfade`] is asked once the fade is over and it takes the place of the
+    /// [`TransitionScene`]. Returns `Transition::None` by default.
+    ///
+    /// If scenes keep redirecting to scenes that redirect in turn, the stack gives up with
+    /// [`SceneError::RedirectLoop`].
+    fn on_enter_transition(
+        &mut self,
+        _ctx: &mut Self::Context,
+    ) -> Result<Transition<Self::Context, E, B>, E> {
+        Ok(Transition::None)
+    }
+    fn on_leave(&mut self, _ctx: &mut Self::Context) -> Result<(), E> {
         Ok(())
     }
-    fn on_pause(
+    /// A transition to apply once this scene has left, E.G. for a boss fight that pushes the
+    /// credits when it's popped. Called straight after `on_leave`, and applied after the rest
+    /// of the transition that removed the scene, so by then the scene below it has been
+    /// unpaused. A scene faded out by a [`Transition::crossfade`] is asked once the fade is over,
+    /// or once the [`TransitionScene`] leaves if that's sooner. Returns `Transition::None` by
+    /// default.
+    ///
+    /// This isn't called when a scene is restarted, when the stack is reset, or when the
+    /// manager is dropped. Follow-ups count towards the same limit as `on_enter_transition`.
+    fn on_leave_transition(
         &mut self,
         _ctx: &mut Self::Context,
-        _should_draw: bool,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<Transition<Self::Context, E, B>, E> {
+        Ok(Transition::None)
+    }
+    /// Called before the scene is popped or replaced by a transition. Return `false` to cancel
+    /// it, E.G. to ask about unsaved changes first.
+    ///
+    /// A cancelled `Pop`, `Replace` or `RemoveAt` does nothing, while `PopN`, `PopTo` and
+    /// `PopWhile` stop at this scene, leaving it on top. `Clear`, `ReplaceAll` and the manager's
+    /// direct methods can't be cancelled. This runs in the middle of applying a transition, so it
+    /// can't change the stack itself: to show a dialog, remember to push one from the next
+    /// `on_update`.
+    fn on_before_leave(&mut self, _ctx: &mut Self::Context) -> Result<bool, E> {
+        Ok(true)
+    }
+    fn on_pause(&mut self, _ctx: &mut Self::Context, _should_draw: bool) -> Result<(), E> {
         Ok(())
     }
-    fn on_unpause(&mut self, _ctx: &mut Self::Context) -> Result<(), Box<dyn Error>> {
+    /// Like `on_pause`, but also receives the [`Scene::pause_reason`] of the scene covering
+    /// this one, if it gave one. Falls back to `on_pause` by default, ignoring the reason.
+    fn on_pause_with(
+        &mut self,
+        ctx: &mut Self::Context,
+        should_draw: bool,
+        _reason: Option<&dyn Any>,
+    ) -> Result<(), E> {
+        self.on_pause(ctx, should_draw)
+    }
+    /// Why this scene is covering the one below it, such as a marker type telling a pause menu
+    /// apart from a loading overlay. Passed to the paused scene's `on_pause_with` whenever this
+    /// scene is pushed over it or replaces it. Returns `None` by default.
+    fn pause_reason(&self) -> Option<&dyn Any> {
+        None
+    }
+    /// Called when the scene above this one is popped, leaving it on top again.
+    ///
+    /// `result` is whatever the popped scene returned from [`Scene::result`]. When several
+    /// scenes are popped at once, it comes from the last of them, the one that was directly
+    /// above this scene.
+    fn on_unpause(
+        &mut self,
+        _ctx: &mut Self::Context,
+        _result: Option<Box<dyn Any>>,
+    ) -> Result<(), E> {
         Ok(())
     }
 
-    fn on_create(&mut self, _ctx: &mut Self::Context) -> sge::ApplicationResult {
+    /// Like `on_unpause`, and told whether the scene was drawn while it was paused, as given by
+    /// `should_draw` in the matching `on_pause`, E.G. to skip relaying out a scene that was
+    /// visible the whole time. Calls `on_unpause` by default.
+    fn on_unpause_with(
+        &mut self,
+        ctx: &mut Self::Context,
+        result: Option<Box<dyn Any>>,
+        was_drawn: bool,
+    ) -> Result<(), E> {
+        let _ = was_drawn;
+        self.on_unpause(ctx, result)
+    }
+
+    /// A value to hand to the scene below this one when it's popped, such as the file chosen
+    /// in a file picker. Called after `on_leave`, and passed to the next scene's `on_unpause`.
+    ///
+    /// This is how a child scene answers the scene that opened it, without going through the
+    /// context. The answer arrives in the same frame the pop is applied: after every scene has
+    /// been updated for a pop returned from an update, or straight away for one applied
+    /// directly, and always before the next draw. Only transitions that uncover the scene
+    /// below, like [`Transition::Pop`], [`Transition::PopN`], [`Transition::PopTo`] and
+    /// [`Transition::PopWhile`], pass it on. If this returns `None`, the last message sent
+    /// through the scene's [`ParentRef`] is passed on instead.
+    fn result(&mut self) -> Option<Box<dyn Any>> {
+        None
+    }
+
+    fn on_create(&mut self, _ctx: &mut Self::Context) -> Result<bool, E> {
         Ok(true)
     }
 
@@ -28,166 +507,3295 @@ pub trait Scene {
         &mut self,
         ctx: &mut Self::Context,
         elapsed_time: f64,
-    ) -> Result<Transition<Self::Context>, Box<dyn Error>>;
+    ) -> Result<Transition<Self::Context, E, B>, E>;
+
+    /// Like `on_update`, but allows queuing any number of transitions, which are applied in
+    /// the order they're pushed onto `transitions`.
+    ///
+    /// The default implementation just pushes the result of `on_update`. Scenes that override
+    /// this can make `on_update` return `Transition::None`, as the manager only calls this.
+    fn on_update_many(
+        &mut self,
+        ctx: &mut Self::Context,
+        elapsed_time: f64,
+        transitions: &mut Vec<Transition<Self::Context, E, B>>,
+    ) -> Result<(), E> {
+        transitions.push(self.on_update(ctx, elapsed_time)?);
+        Ok(())
+    }
+
+    /// Check on some work the scene is doing in the background, such as loading assets, and
+    /// return a transition once it's done. Called straight after every update, so nothing
+    /// ever blocks on it. Returns `Poll::Pending` by default.
+    ///
+    /// There's no waker: a scene driving a future polls it by hand here, once per frame. That
+    /// keeps everything on one thread, so any heavy work still has to be split into chunks
+    /// small enough to fit in a frame, or moved to a thread the future waits on.
+    fn poll_ready(
+        &mut self,
+        _ctx: &mut Self::Context,
+    ) -> Result<Poll<Transition<Self::Context, E, B>>, E> {
+        Ok(Poll::Pending)
+    }
+
+    /// How often to call `on_tick`, in the same units as `elapsed_time`, for logic that should
+    /// run at a steady rate, such as AI decisions. Returns `None` by default, for no ticks.
+    ///
+    /// Time is only counted while the scene is being updated, and starts over for every scene
+    /// whenever a transition changes the stack.
+    fn tick_interval(&self) -> Option<f64> {
+        None
+    }
+
+    /// Called once for each `tick_interval` that has passed, just before `on_update`, so
+    /// possibly several times in a slow frame.
+    fn on_tick(&mut self, _ctx: &mut Self::Context) -> Result<(), E> {
+        Ok(())
+    }
+
+    /// Roughly how long, in seconds of real time, this scene's update takes, for skipping it on
+    /// frames that are running out of time, see [`SceneStack::set_frame_budget`]. Returns `None`
+    /// by default, so the scene is never skipped.
+    fn update_budget(&self) -> Option<f64> {
+        None
+    }
+
+    /// Whether this scene should be updated through `on_update_with_below`, so it can read the
+    /// scene beneath it, instead of `on_update_many`.
+    fn wants_below(&self) -> bool {
+        false
+    }
+
+    /// Like `on_update`, but with shared access to the scene directly beneath this one, E.G. so
+    /// a HUD can read the state of the world under it. Downcast `below` to get at it. Only called
+    /// if `wants_below` returns `true`, and falls back to `on_update` by default.
+    fn on_update_with_below(
+        &mut self,
+        ctx: &mut Self::Context,
+        elapsed_time: f64,
+        _below: Option<&dyn Scene<E, B, Context = Self::Context>>,
+    ) -> Result<Transition<Self::Context, E, B>, E> {
+        self.on_update(ctx, elapsed_time)
+    }
+
+    /// Called when `on_update` (or whichever update method the scene uses) or `on_event`
+    /// fails, giving the scene a chance to recover, E.G. by replacing itself with an error
+    /// screen. The transition returned is queued as if the update or event had returned it,
+    /// with a failed event counting as ignored.
+    ///
+    /// Returns the error by default, failing the frame as usual. An error returned from here is
+    /// never passed back to `on_error`.
+    fn on_error(
+        &mut self,
+        _ctx: &mut Self::Context,
+        err: E,
+    ) -> Result<Transition<Self::Context, E, B>, E> {
+        Err(err)
+    }
+
+    /// Called zero or more times per frame with a constant `dt`, before `on_update`, when the
+    /// manager has a fixed timestep set (see [`SceneManager::set_fixed_timestep`]).
+    fn on_fixed_update(&mut self, _ctx: &mut Self::Context, _dt: f64) -> Result<(), E> {
+        Ok(())
+    }
 
     fn on_event(
         &mut self,
         _ctx: &mut Self::Context,
-        _event: &sge::Event,
-    ) -> Result<(bool, Transition<Self::Context>), Box<dyn Error>> {
+        _event: &B::Event,
+    ) -> Result<(bool, Transition<Self::Context, E, B>), E> {
         Ok((false, Transition::None))
     }
 
-    fn draw_next(&self, _ctx: &mut Self::Context) -> bool {
+    /// Like `on_event`, but lets the scene say it handled the event while still letting the
+    /// scenes below it see it.
+    ///
+    /// The default implementation maps the `bool` from `on_event` onto [`EventFlow::Consume`]
+    /// or [`EventFlow::Ignore`], so events stop at the first scene that handles them, as usual.
+    /// Scenes that override this can leave `on_event` alone, as the manager only calls this.
+    fn on_event_flow(
+        &mut self,
+        ctx: &mut Self::Context,
+        event: &B::Event,
+    ) -> EventFlowResult<Self::Context, E, B> {
+        let (handled, trans) = self.on_event(ctx, event)?;
+        Ok((handled.into(), trans))
+    }
+
+    /// The categories of event this scene wants passed to `on_event`. Events outside of it skip
+    /// the scene as if it ignored them, but still stop at it if it's modal or doesn't want the
+    /// scene below drawn. Window changes are passed to `on_resize` and friends regardless.
+    /// Accepts everything by default.
+    fn event_mask(&self) -> EventMask {
+        EventMask::ALL
+    }
+
+    /// Called on every scene in the stack, not just those that would see the event, whenever the
+    /// window changes size. The event is still passed to `on_event` as normal afterwards.
+    fn on_resize(&mut self, _ctx: &mut Self::Context, _width: u32, _height: u32) -> Result<(), E> {
+        Ok(())
+    }
+
+    /// Called on every scene in the stack when the window loses focus, E.G. to pause the game.
+    ///
+    /// This isn't the same as `on_pause`, which is about being covered by another scene.
+    fn on_background(&mut self, _ctx: &mut Self::Context) -> Result<(), E> {
+        Ok(())
+    }
+
+    /// Called on every scene in the stack when the window gains focus again.
+    fn on_foreground(&mut self, _ctx: &mut Self::Context) -> Result<(), E> {
+        Ok(())
+    }
+
+    /// Handle an application-specific message sent with [`SceneManager::send_to`], returning
+    /// whether it was handled. Downcast `msg` to find out what it is.
+    fn on_message(&mut self, _ctx: &mut Self::Context, _msg: &dyn Any) -> Result<bool, E> {
+        Ok(false)
+    }
+
+    /// Draw the scene. Called after every scene has been updated, from the bottom of the
+    /// visible part of the stack to the top, so overlays are drawn over what's beneath them.
+    fn on_draw(&mut self, _ctx: &mut Self::Context) -> Result<(), E> {
+        Ok(())
+    }
+
+    /// Draw the scene at an opacity from 0 to 1. The stack passes 1, except during a
+    /// [`Transition::Fade`], where it works out the opacity from each scene's part in the fade:
+    /// the scene fading out goes from 1 to 0, and the one fading in from 0 to 1. A scene drawing
+    /// others inside it, like a [`TransitionScene`], passes each one the opacity it should be
+    /// drawn at too. Calls `on_draw` by default, ignoring `alpha`.
+    fn on_draw_with_alpha(&mut self, ctx: &mut Self::Context, alpha: f64) -> Result<(), E> {
+        let _ = alpha;
+        self.on_draw(ctx)
+    }
+
+    /// Whether the scene below this one should still be drawn (and updated) while this one is on
+    /// top of it.
+    ///
+    /// This can be called several times per frame, so it should be cheap and free of side
+    /// effects, which is why it only gets shared access to the context.
+    fn draw_next(&self, _ctx: &Self::Context) -> bool {
+        false
+    }
+
+    /// Whether events this scene doesn't consume should fall through to the scene below, E.G.
+    /// for an opaque HUD that still lets clicks through to the world. Returns `draw_next` by
+    /// default. A modal scene never passes events below, whatever this returns.
+    fn passes_events_below(&self, ctx: &Self::Context) -> bool {
+        self.draw_next(ctx)
+    }
+
+    /// Whether this scene blocks the scenes below it from being updated or seeing events, such
+    /// as a confirmation dialog that pauses the world. Scenes below are still drawn as usual,
+    /// according to `draw_next`.
+    fn is_modal(&self) -> bool {
+        false
+    }
+
+    /// Whether this scene should be updated every frame, even when a scene above it is modal or
+    /// doesn't want it drawn, such as a scene that keeps a network connection alive.
+    ///
+    /// Scenes are still updated from the top of the stack down: first every scene reached
+    /// through `draw_next` as usual, then, continuing down, only the scenes below that return
+    /// `true` here. This covers both `on_update` and `on_fixed_update`, but not events or
+    /// drawing.
+    fn always_update(&self) -> bool {
         false
     }
+
+    /// How the cursor should be while this scene is on top, applied through the callback set
+    /// with [`SceneManager::set_on_cursor`] whenever a different scene comes to the top. Returns
+    /// [`CursorMode::Inherit`] by default, leaving the cursor alone.
+    fn cursor(&self) -> CursorMode {
+        CursorMode::Inherit
+    }
+
+    /// A human-readable name for this scene, used for debugging and introspection.
+    fn name(&self) -> &str {
+        "unnamed"
+    }
+
+    /// This scene's state, to be saved along with its name by
+    /// [`SceneManager::serialize_stack`]. Returns `None` by default.
+    #[cfg(feature = "serde")]
+    fn save_state(&self) -> Option<serde_json::Value> {
+        None
+    }
 }
 
-pub enum Transition<C> {
+pub enum Transition<C, E = BoxError, B: Backend = SgeBackend> {
     None,
-    Push(Box<dyn Scene<Context = C>>),
+    Push(BoxScene<C, E, B>),
     Pop,
-    Replace(Box<dyn Scene<Context = C>>),
+    /// Pop up to `n` scenes at once.
+    ///
+    /// `on_leave` is called on each popped scene from top to bottom, then `on_unpause` is
+    /// called once on the scene left on top (if any), after all of the leaves have run. If `n`
+    /// is larger than the stack, every scene is popped and the manager quits on the next update.
+    PopN(usize),
+    /// Pop scenes until the predicate returns `true` for the scene on top.
+    ///
+    /// The current top is checked first, so if it already matches nothing happens. Otherwise
+    /// `on_leave` is called on each popped scene, and the matching scene gets a single
+    /// `on_unpause` once it's uncovered. If no scene matches, the stack is cleared and the
+    /// manager quits on the next update.
+    PopTo(ScenePredicate<C, E, B>),
+    /// Pop scenes for as long as the predicate returns `true` for the scene on top, given the
+    /// context too, E.G. to unwind transient scenes unless the context says to stop early.
+    ///
+    /// Like `PopTo` the other way round: `on_leave` is called on each popped scene, and the
+    /// scene left on top gets a single `on_unpause`. A predicate that's `true` for every scene
+    /// clears the stack, and the manager quits on the next update.
+    PopWhile(SceneContextPredicate<C, E, B>),
+    /// Replace the top scene. Fails with [`SceneError::EmptyStack`] if there isn't one.
+    Replace(BoxScene<C, E, B>),
+    /// Replace the top scene, like [`Transition::Replace`], then keep drawing the old one under
+    /// the new one for `duration`, in the same units as `elapsed_time`, as one fades out and
+    /// the other fades in, see [`Scene::on_draw_with_alpha`].
+    ///
+    /// Unlike a [`Transition::crossfade`], this needs no wrapper scene: the new scene is entered
+    /// and updated straight away, and the old one has already been left, so it's only drawn.
+    Fade {
+        scene: BoxScene<C, E, B>,
+        duration: f64,
+    },
+    /// Pop every scene, calling `on_leave` on each from top to bottom.
+    ///
+    /// Since nothing is left to uncover, no `on_unpause` is fired, and the manager quits on the
+    /// next update.
+    Clear,
+    /// Pop every scene, calling `on_leave` on each from top to bottom, then enter and push the
+    /// new scene.
+    ReplaceAll(BoxScene<C, E, B>),
+    /// Exchange the top two scenes, without entering or leaving either of them.
+    ///
+    /// The scene moving down gets `on_pause` with `should_draw` set to `false`, and the scene
+    /// coming up gets `on_unpause`, so both keep their state. Does nothing if there are fewer
+    /// than two scenes.
+    Swap,
+    /// Push several scenes at once, ending with the last one on top.
+    ///
+    /// Each scene is entered and pushed in order, just as if they'd been pushed one by one: the
+    /// scene on top is paused before each push, with `should_draw` taken from the `draw_next` of
+    /// the scene going over it. So the old top is paused once, and each new scene except the
+    /// last is paused by the one after it.
+    PushAll(Vec<BoxScene<C, E, B>>),
+    /// Enter a scene and insert it at index `depth`, where 0 is the bottom of the stack.
+    ///
+    /// Since the scene on top doesn't change, no scene is paused or unpaused, including the
+    /// inserted one, which never gets `on_pause` even though it's covered. A `depth` at or past
+    /// the top of the stack is treated as a [`Transition::Push`].
+    Insert {
+        depth: usize,
+        scene: BoxScene<C, E, B>,
+    },
+    /// Remove the scene at the given index, where 0 is the bottom of the stack, calling its
+    /// `on_leave`.
+    ///
+    /// Removing a scene beneath the top doesn't disturb the top scene. Removing the top scene
+    /// is the same as a [`Transition::Pop`]. Either way, the scene can cancel its removal in
+    /// `on_before_leave`. Does nothing if the index is out of bounds.
+    RemoveAt(usize),
+    /// Like [`Transition::Push`], but enters the scene with `on_enter_with`, handing it
+    /// `payload`.
+    PushWith {
+        scene: BoxScene<C, E, B>,
+        payload: Box<dyn Any>,
+    },
+    /// Like [`Transition::Push`], but builds the scene by calling the closure when the
+    /// transition is applied, so the scene isn't allocated unless it runs.
+    PushFn(Box<dyn FnMut() -> BoxScene<C, E, B>>),
+    /// Take the top scene off the stack, pass it through the closure, and put what it returns
+    /// back in its place, E.G. to wrap it in a decorator.
+    ///
+    /// This is treated as the same scene, so no lifecycle callbacks are fired on either the
+    /// old scene or the new one. Fails with [`SceneError::EmptyStack`] if there's no scene to
+    /// map.
+    Map(SceneMapper<C, E, B>),
+    /// Like [`Transition::Map`], but the scene the closure returns gets [`Scene::on_seed`] and
+    /// [`Scene::on_parent`], as though the stack had entered it, for wrappers that enter a new
+    /// scene of their own such as a [`TransitionScene`]. See [`Transition::crossfade`].
+    Crossfade(SceneMapper<C, E, B>),
+    /// Call `on_leave` and then `on_enter` on the top scene, keeping the same instance in place,
+    /// E.G. to retry a level. Does nothing if the stack is empty.
+    Restart,
+    /// Pop every scene, calling `on_leave` on each from top to bottom, like
+    /// [`Transition::Clear`], and quit at the end of the frame, even if other transitions push
+    /// more scenes in the meantime.
+    Quit,
+    /// Apply `then` if `cond` returns `true` for the context at the time the transition is
+    /// applied, and `otherwise` if not.
+    ///
+    /// Nested conditions are resolved in a loop rather than recursively, so they can be nested
+    /// arbitrarily deep.
+    If {
+        cond: Box<dyn FnOnce(&C) -> bool>,
+        then: Box<Transition<C, E, B>>,
+        otherwise: Box<Transition<C, E, B>>,
+    },
+    /// Apply several transitions of any kind, one after the other.
+    ///
+    /// Nested sequences are flattened as they're applied rather than recursed into, so they can
+    /// be nested arbitrarily deep.
+    Sequence(Vec<Transition<C, E, B>>),
 }
 
-impl<C> Transition<C> {
-    pub fn push(s: impl Scene<Context = C> + 'static) -> Self {
-        Self::Push(Box::new(s))
+/// [`Transition::None`], so scenes that rarely transition can return `Ok(Default::default())`.
+impl<C, E, B: Backend> Default for Transition<C, E, B> {
+    fn default() -> Self {
+        Self::None
     }
+}
 
-    pub fn replace(s: impl Scene<Context = C> + 'static) -> Self {
-        Self::Replace(Box::new(s))
+/// Shows the [`TransitionKind`], as the scenes it holds can't be printed.
+impl<C, E, B: Backend> fmt::Debug for Transition<C, E, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.kind(), f)
     }
+}
 
-    fn apply_to(
-        self,
-        scenes: &mut Vec<Box<dyn Scene<Context = C>>>,
-        ctx: &mut C,
-    ) -> Result<(), Box<dyn Error>> {
-        match self {
-            Transition::Push(mut new) => {
-                if let Some(last) = scenes.last_mut() {
-                    let draw_next = new.draw_next(ctx);
-                    last.on_pause(ctx, draw_next)?;
-                }
-                new.on_enter(ctx)?;
-                scenes.push(new);
-            }
-            Transition::Pop => {
-                if let Some(mut old) = scenes.pop() {
-                    old.on_leave(ctx)?;
-                }
-                if let Some(last) = scenes.last_mut() {
-                    last.on_unpause(ctx)?;
-                }
-            }
-            Transition::Replace(mut new) => {
-                let last = scenes
-                    .last_mut()
-                    .expect("Tried to replace a scene that does not exist");
+/// Describes a transition without holding any of its scenes, for observing changes to the stack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TransitionKind {
+    None,
+    Push,
+    Pop,
+    PopN(usize),
+    PopTo,
+    PopWhile,
+    Replace,
+    Fade,
+    Clear,
+    ReplaceAll,
+    Swap,
+    /// Pushed this many scenes
+    PushAll(usize),
+    Insert {
+        depth: usize,
+    },
+    RemoveAt(usize),
+    /// A scene below the top was replaced with [`SceneManager::replace_at`]
+    ReplaceAt {
+        depth: usize,
+    },
+    PushWith,
+    PushFn,
+    Map,
+    Crossfade,
+    Restart,
+    Quit,
+    If,
+    /// Applied a sequence of this many transitions
+    Sequence(usize),
+}
 
-                let draw_next = new.draw_next(ctx);
-                last.on_pause(ctx, draw_next)?;
+impl TransitionKind {
+    fn is_pop(self) -> bool {
+        matches!(
+            self,
+            Self::Pop
+                | Self::PopN(_)
+                | Self::PopTo
+                | Self::PopWhile
+                | Self::Clear
+                | Self::RemoveAt(_)
+        )
+    }
 
-                new.on_enter(ctx)?;
-                let mut old = std::mem::replace(last, new);
-                old.on_leave(ctx)?;
-            }
-            _ => {}
+    fn is_push(self) -> bool {
+        matches!(
+            self,
+            Self::Push | Self::PushAll(_) | Self::Insert { .. } | Self::PushWith | Self::PushFn
+        )
+    }
+
+    /// Whether a transition of this kind put a different scene on top, given how many scenes
+    /// were on the stack before and after it, and whether the top scene's handle changed.
+    fn changes_top(self, old_len: usize, new_len: usize, top_moved: bool) -> bool {
+        match self {
+            // Conditions are resolved before they're applied, so never get this far
+            Self::None | Self::ReplaceAt { .. } | Self::Map | Self::Crossfade | Self::If => false,
+            Self::Pop | Self::PopN(_) | Self::PopTo | Self::PopWhile => new_len != old_len,
+            Self::Swap => old_len >= 2,
+            Self::Restart | Self::Quit => old_len > 0,
+            Self::PushAll(n) => n > 0,
+            Self::Insert { depth } => depth >= old_len,
+            Self::RemoveAt(index) => old_len.checked_sub(1) == Some(index),
+            // Its parts may cancel out, or not touch the top at all
+            Self::Sequence(_) => top_moved,
+            Self::Push
+            | Self::PushWith
+            | Self::PushFn
+            | Self::Replace
+            | Self::Fade
+            | Self::Clear
+            | Self::ReplaceAll => true,
         }
-        Ok(())
     }
 }
 
-pub struct SceneManager<C> {
-    scenes: Vec<Box<dyn Scene<Context = C>>>,
-    operations: Vec<Transition<C>>,
-    /// The context, passed to scenes each loop iteration
-    pub ctx: C,
+/// Pushes the scene, like [`Transition::Push`].
+impl<C, E, B: Backend> From<BoxScene<C, E, B>> for Transition<C, E, B> {
+    fn from(scene: BoxScene<C, E, B>) -> Self {
+        Self::Push(scene)
+    }
 }
 
-impl<C> SceneManager<C> {
-    pub fn new(ctx: C, base_scene: Box<dyn Scene<Context = C>>) -> Self {
-        Self {
-            ctx,
-            scenes: vec![base_scene],
-            // Kept around to avoid allocating on every frame
-            operations: Vec::new(),
-        }
+impl<C, E, B: Backend> Transition<C, E, B> {
+    pub fn none() -> Self {
+        Self::None
     }
 
-    pub fn apply(&mut self, trans: Transition<C>) -> Result<(), Box<dyn Error>> {
-        trans.apply_to(&mut self.scenes, &mut self.ctx)
+    pub fn pop() -> Self {
+        Self::Pop
     }
 
-    pub fn push(&mut self, mut new: Box<dyn Scene<Context = C>>) -> Result<(), Box<dyn Error>> {
-        new.on_enter(&mut self.ctx)?;
-        self.scenes.push(new);
-        Ok(())
+    pub fn push(s: impl Scene<E, B, Context = C> + 'static) -> Self {
+        Self::Push(Box::new(s))
     }
 
-    pub fn pop(&mut self) -> Result<Option<Box<dyn Scene<Context = C>>>, Box<dyn Error>> {
-        let mut old = self.scenes.pop();
-        if let Some(ref mut old) = old {
-            old.on_leave(&mut self.ctx)?;
-        }
-        Ok(old)
+    pub fn replace(s: impl Scene<E, B, Context = C> + 'static) -> Self {
+        Self::Replace(Box::new(s))
     }
 
-    pub fn replace(
-        &mut self,
-        mut new: Box<dyn Scene<Context = C>>,
-    ) -> Result<Box<dyn Scene<Context = C>>, Box<dyn Error>> {
-        let last = self
-            .scenes
-            .last_mut()
-            .expect("Tried to replace a scene that does not exist");
-        new.on_enter(&mut self.ctx)?;
-        let mut old = std::mem::replace(last, new);
-        old.on_leave(&mut self.ctx)?;
-        Ok(old)
+    /// Like [`Transition::replace`], for a scene that's already boxed.
+    pub fn replace_boxed(s: BoxScene<C, E, B>) -> Self {
+        Self::Replace(s)
     }
-}
 
-impl<C> sge::Application for SceneManager<C> {
-    fn on_create(&mut self) -> sge::ApplicationResult {
-        for scene in &mut self.scenes {
-            if !scene.on_create(&mut self.ctx)? {
-                return Ok(false);
-            }
+    /// A [`Transition::Fade`].
+    pub fn fade(s: impl Scene<E, B, Context = C> + 'static, duration: f64) -> Self {
+        Self::Fade {
+            scene: Box::new(s),
+            duration,
         }
-        // If there are no scenes, quit
-        Ok(!self.scenes.is_empty())
     }
 
-    fn on_update(&mut self, elapsed_time: f64) -> sge::ApplicationResult {
-        for scene in self.scenes.iter_mut().rev() {
-            let trans = scene.on_update(&mut self.ctx, elapsed_time)?;
-            self.operations.push(trans);
-            if !scene.draw_next(&mut self.ctx) {
-                break;
-            }
+    pub fn replace_all(s: impl Scene<E, B, Context = C> + 'static) -> Self {
+        Self::ReplaceAll(Box::new(s))
+    }
+
+    pub fn push_with(s: impl Scene<E, B, Context = C> + 'static, payload: impl Any) -> Self {
+        Self::PushWith {
+            scene: Box::new(s),
+            payload: Box::new(payload),
         }
-        for op in self.operations.drain(..) {
-            op.apply_to(&mut self.scenes, &mut self.ctx)?;
+    }
+
+    pub fn push_fn<S: Scene<E, B, Context = C> + 'static>(
+        mut f: impl FnMut() -> S + 'static,
+    ) -> Self {
+        Self::PushFn(Box::new(move || Box::new(f())))
+    }
+
+    pub fn map(f: impl FnOnce(BoxScene<C, E, B>) -> BoxScene<C, E, B> + 'static) -> Self {
+        Self::Map(Box::new(f))
+    }
+
+    /// A [`Transition::If`].
+    pub fn when(cond: impl FnOnce(&C) -> bool + 'static, then: Self, otherwise: Self) -> Self {
+        Self::If {
+            cond: Box::new(cond),
+            then: Box::new(then),
+            otherwise: Box::new(otherwise),
         }
-        // If there are no more scenes, quit
-        Ok(!self.scenes.is_empty())
     }
 
-    fn on_event(&mut self, event: &sge::Event) -> sge::ApplicationResult {
-        let mut was_handled = false;
-        for scene in self.scenes.iter_mut().rev() {
-            let (handled, trans) = scene.on_event(&mut self.ctx, event)?;
-            was_handled |= handled;
-            self.operations.push(trans);
-            if handled || !scene.draw_next(&mut self.ctx) {
+    /// Fade from the top scene to `next` over `duration`, with a [`TransitionScene`]. Fails with
+    /// [`SceneError::EmptyStack`] if there's no scene to fade from.
+    ///
+    /// To blend the scenes as they're drawn, build the [`TransitionScene`] in a
+    /// [`Transition::Crossfade`] yourself, with [`TransitionScene::with_blend`]. To fade without
+    /// a wrapper scene, use a [`Transition::Fade`].
+    pub fn crossfade(s: impl Scene<E, B, Context = C> + 'static, duration: f64) -> Self
+    where
+        C: 'static,
+        E: From<SceneError> + 'static,
+    {
+        Self::crossfade_with(s, duration, |t| t)
+    }
+
+    /// Like [`Transition::crossfade`], with an easing function, see
+    /// [`TransitionScene::with_easing`].
+    pub fn crossfade_with(
+        s: impl Scene<E, B, Context = C> + 'static,
+        duration: f64,
+        easing: fn(f64) -> f64,
+    ) -> Self
+    where
+        C: 'static,
+        E: From<SceneError> + 'static,
+    {
+        let next = Box::new(s);
+        Self::Crossfade(Box::new(move |old| {
+            Box::new(TransitionScene::new(old, next, duration).with_easing(easing))
+        }))
+    }
+
+    /// Push a menu over the current scene, wrapped in a [`PauseMenu`], so the scenes below keep
+    /// being drawn but stop updating until the menu pops itself.
+    pub fn pause_menu<S: Scene<E, B, Context = C> + 'static>(menu: S) -> Self {
+        Self::push(PauseMenu::new(menu))
+    }
+
+    /// Push several scenes as one level of the stack, wrapped in a [`SceneGroup`], so they're
+    /// all updated and drawn together, and a single pop removes them all.
+    pub fn push_group(scenes: Vec<BoxScene<C, E, B>>) -> Self
+    where
+        C: 'static,
+        E: 'static,
+    {
+        Self::push(SceneGroup::new(scenes))
+    }
+
+    pub fn pop_to(pred: impl FnMut(&dyn Scene<E, B, Context = C>) -> bool + 'static) -> Self {
+        Self::PopTo(Box::new(pred))
+    }
+
+    pub fn pop_while(
+        pred: impl FnMut(&dyn Scene<E, B, Context = C>, &C) -> bool + 'static,
+    ) -> Self {
+        Self::PopWhile(Box::new(pred))
+    }
+
+    pub fn kind(&self) -> TransitionKind {
+        match self {
+            Self::None => TransitionKind::None,
+            Self::Push(_) => TransitionKind::Push,
+            Self::Pop => TransitionKind::Pop,
+            Self::PopN(n) => TransitionKind::PopN(*n),
+            Self::PopTo(_) => TransitionKind::PopTo,
+            Self::PopWhile(_) => TransitionKind::PopWhile,
+            Self::Replace(_) => TransitionKind::Replace,
+            Self::Fade { .. } => TransitionKind::Fade,
+            Self::Clear => TransitionKind::Clear,
+            Self::ReplaceAll(_) => TransitionKind::ReplaceAll,
+            Self::Swap => TransitionKind::Swap,
+            Self::PushAll(scenes) => TransitionKind::PushAll(scenes.len()),
+            Self::Insert { depth, .. } => TransitionKind::Insert { depth: *depth },
+            Self::RemoveAt(index) => TransitionKind::RemoveAt(*index),
+            Self::PushWith { .. } => TransitionKind::PushWith,
+            Self::PushFn(_) => TransitionKind::PushFn,
+            Self::Map(_) => TransitionKind::Map,
+            Self::Crossfade(_) => TransitionKind::Crossfade,
+            Self::Restart => TransitionKind::Restart,
+            Self::Quit => TransitionKind::Quit,
+            Self::If { .. } => TransitionKind::If,
+            Self::Sequence(seq) => TransitionKind::Sequence(seq.len()),
+        }
+    }
+
+    /// Pick the branch of any conditions, until reaching a transition that isn't one.
+    fn resolve(self, ctx: &C) -> Self {
+        let mut trans = self;
+        while let Transition::If {
+            cond,
+            then,
+            otherwise,
+        } = trans
+        {
+            trans = if cond(ctx) { *then } else { *otherwise };
+        }
+        trans
+    }
+
+    /// Apply this transition, failing with [`SceneError::DepthExceeded`] before entering any
+    /// scene if it would grow the stack past `max_depth`. Sets `quit` if it's, or contains, a
+    /// [`Transition::Quit`], and collects the `on_leave_transition`s of the scenes it leaves in
+    /// `after_leave`. Each scene it enters gets the next seed from `seeds`, if it's set.
+    ///
+    /// Returns whether the stack changed, which it doesn't if a scene cancelled the transition
+    /// in `on_before_leave`, or there was nothing for it to do.
+    fn apply_to(
+        self,
+        scenes: &mut Vec<Entry<C, E, B>>,
+        ctx: &mut C,
+        max_depth: Option<usize>,
+        quit: &mut bool,
+        after_leave: &mut Vec<Transition<C, E, B>>,
+        seeds: &mut Option<u64>,
+    ) -> Result<bool, E>
+    where
+        E: From<SceneError>,
+    {
+        match self.resolve(ctx) {
+            Transition::Push(mut new) => {
+                check_depth(scenes.len(), 1, max_depth)?;
+                if let Some(last) = scenes.last_mut() {
+                    let draw_next = new.draw_next(ctx);
+                    last.pause(ctx, draw_next, new.pause_reason())?;
+                }
+                new.enter(ctx, next_seed(seeds))?;
+                scenes.push(Entry::new(new));
+            }
+            Transition::Pop => {
+                let Some(last) = scenes.last_mut() else {
+                    return Ok(false);
+                };
+                if !last.before_leave(ctx)? {
+                    return Ok(false);
+                }
+                let mut result = None;
+                if let Some(mut old) = scenes.pop() {
+                    old.leave(ctx, after_leave)?;
+                    result = old.take_result();
+                }
+                if let Some(last) = scenes.last_mut() {
+                    last.unpause(ctx, result)?;
+                }
+            }
+            Transition::PopN(n) => {
+                let n = n.min(scenes.len());
+                let mut popped = 0;
+                let mut result = None;
+                while popped < n {
+                    let Some(last) = scenes.last_mut() else {
+                        break;
+                    };
+                    if !last.before_leave(ctx)? {
+                        break;
+                    }
+                    if let Some(mut old) = scenes.pop() {
+                        old.leave(ctx, after_leave)?;
+                        result = old.take_result();
+                    }
+                    popped += 1;
+                }
+                if popped == 0 {
+                    return Ok(false);
+                }
+                if let Some(last) = scenes.last_mut() {
+                    last.unpause(ctx, result)?;
+                }
+            }
+            Transition::PopTo(mut pred) => {
+                let mut popped = false;
+                let mut result = None;
+                while let Some(last) = scenes.last_mut() {
+                    if pred(&**last) || !last.before_leave(ctx)? {
+                        break;
+                    }
+                    if let Some(mut old) = scenes.pop() {
+                        old.leave(ctx, after_leave)?;
+                        result = old.take_result();
+                    }
+                    popped = true;
+                }
+                if !popped {
+                    return Ok(false);
+                }
+                if let Some(last) = scenes.last_mut() {
+                    last.unpause(ctx, result)?;
+                }
+            }
+            Transition::PopWhile(mut pred) => {
+                let mut popped = false;
+                let mut result = None;
+                while let Some(last) = scenes.last_mut() {
+                    if !pred(&**last, ctx) || !last.before_leave(ctx)? {
+                        break;
+                    }
+                    if let Some(mut old) = scenes.pop() {
+                        old.leave(ctx, after_leave)?;
+                        result = old.take_result();
+                    }
+                    popped = true;
+                }
+                if !popped {
+                    return Ok(false);
+                }
+                if let Some(last) = scenes.last_mut() {
+                    last.unpause(ctx, result)?;
+                }
+            }
+            Transition::Replace(new) => {
+                return Ok(replace_top(scenes, ctx, new, after_leave, seeds)?.is_some());
+            }
+            Transition::Fade { scene, duration } => {
+                let Some(old) = replace_top(scenes, ctx, scene, after_leave, seeds)? else {
+                    return Ok(false);
+                };
+                if let Some(last) = scenes.last_mut() {
+                    last.fade = Some(Fade {
+                        outgoing: old,
+                        elapsed: 0.0,
+                        duration,
+                    });
+                }
+            }
+            Transition::Clear => {
+                if scenes.is_empty() {
+                    return Ok(false);
+                }
+                while let Some(mut old) = scenes.pop() {
+                    old.leave(ctx, after_leave)?;
+                }
+            }
+            Transition::Quit => {
+                *quit = true;
+                while let Some(mut old) = scenes.pop() {
+                    old.leave(ctx, after_leave)?;
+                }
+            }
+            Transition::ReplaceAll(mut new) => {
+                while let Some(mut old) = scenes.pop() {
+                    old.leave(ctx, after_leave)?;
+                }
+                new.enter(ctx, next_seed(seeds))?;
+                scenes.push(Entry::new(new));
+            }
+            Transition::Swap => {
+                let [.., below, top] = scenes.as_mut_slice() else {
+                    return Ok(false);
+                };
+                top.pause(ctx, false, None)?;
+                std::mem::swap(below, top);
+                top.unpause(ctx, None)?;
+            }
+            Transition::PushAll(new) => {
+                check_depth(scenes.len(), new.len(), max_depth)?;
+                for mut new in new {
+                    if let Some(last) = scenes.last_mut() {
+                        let draw_next = new.draw_next(ctx);
+                        last.pause(ctx, draw_next, new.pause_reason())?;
+                    }
+                    new.enter(ctx, next_seed(seeds))?;
+                    scenes.push(Entry::new(new));
+                }
+            }
+            Transition::Insert { depth, mut scene } => {
+                if depth >= scenes.len() {
+                    return Transition::Push(scene).apply_to(
+                        scenes,
+                        ctx,
+                        max_depth,
+                        quit,
+                        after_leave,
+                        seeds,
+                    );
+                }
+                check_depth(scenes.len(), 1, max_depth)?;
+                scene.enter(ctx, next_seed(seeds))?;
+                scenes.insert(depth, Entry::new(scene));
+            }
+            Transition::RemoveAt(index) => {
+                if scenes.len().checked_sub(1) == Some(index) {
+                    return Transition::Pop.apply_to(
+                        scenes,
+                        ctx,
+                        max_depth,
+                        quit,
+                        after_leave,
+                        seeds,
+                    );
+                }
+                let Some(scene) = scenes.get_mut(index) else {
+                    return Ok(false);
+                };
+                if !scene.before_leave(ctx)? {
+                    return Ok(false);
+                }
+                scenes.remove(index).leave(ctx, after_leave)?;
+            }
+            Transition::PushWith { mut scene, payload } => {
+                check_depth(scenes.len(), 1, max_depth)?;
+                if let Some(last) = scenes.last_mut() {
+                    let draw_next = scene.draw_next(ctx);
+                    last.pause(ctx, draw_next, scene.pause_reason())?;
+                }
+                scene.enter_with(ctx, payload, next_seed(seeds))?;
+                scenes.push(Entry::new(scene));
+            }
+            Transition::PushFn(mut f) => {
+                return Transition::Push(f()).apply_to(
+                    scenes,
+                    ctx,
+                    max_depth,
+                    quit,
+                    after_leave,
+                    seeds,
+                );
+            }
+            Transition::Map(f) => {
+                let old = scenes.pop().ok_or(SceneError::EmptyStack)?;
+                // Keeps its handle, as it's the same scene
+                scenes.push(Entry {
+                    handle: old.handle,
+                    scene: f(old.scene),
+                    was_drawn: old.was_drawn,
+                    fresh: old.fresh,
+                    fade: old.fade,
+                    message: old.message,
+                });
+            }
+            Transition::Crossfade(f) => {
+                let old = scenes.pop().ok_or(SceneError::EmptyStack)?;
+                let mut scene = f(old.scene);
+                scene.seed(next_seed(seeds));
+                scene.on_parent(ParentRef(Rc::downgrade(&old.message)));
+                scenes.push(Entry {
+                    handle: old.handle,
+                    scene,
+                    was_drawn: old.was_drawn,
+                    fresh: old.fresh,
+                    fade: old.fade,
+                    message: old.message,
+                });
+            }
+            Transition::Restart => {
+                let Some(last) = scenes.last_mut() else {
+                    return Ok(false);
+                };
+                // It's coming straight back, so it doesn't get `on_leave_transition`
+                last.scene.leave(ctx)?;
+                last.enter(ctx, next_seed(seeds))?;
+                last.fresh = true;
+                // Anything it said before starting over no longer stands
+                last.message.borrow_mut().take();
+            }
+            Transition::Sequence(seq) => {
+                let mut changed = false;
+                let mut pending = vec![seq.into_iter()];
+                while let Some(seq) = pending.last_mut() {
+                    match seq.next().map(|t| t.resolve(ctx)) {
+                        Some(Transition::Sequence(inner)) => pending.push(inner.into_iter()),
+                        Some(trans) => {
+                            changed |=
+                                trans.apply_to(scenes, ctx, max_depth, quit, after_leave, seeds)?
+                        }
+                        None => {
+                            pending.pop();
+                        }
+                    }
+                }
+                return Ok(changed);
+            }
+            Transition::None | Transition::If { .. } => return Ok(false),
+        }
+        Ok(true)
+    }
+}
+
+/// Replace the top scene as [`Transition::Replace`] does, returning the old one, or `None` if it
+/// cancelled in `on_before_leave`.
+fn replace_top<C, E: From<SceneError>, B: Backend>(
+    scenes: &mut [Entry<C, E, B>],
+    ctx: &mut C,
+    mut new: BoxScene<C, E, B>,
+    after_leave: &mut Vec<Transition<C, E, B>>,
+    seeds: &mut Option<u64>,
+) -> Result<Option<BoxScene<C, E, B>>, E> {
+    let last = scenes.last_mut().ok_or(SceneError::EmptyStack)?;
+    if !last.before_leave(ctx)? {
+        return Ok(None);
+    }
+
+    let draw_next = new.draw_next(ctx);
+    last.pause(ctx, draw_next, new.pause_reason())?;
+
+    new.enter(ctx, next_seed(seeds))?;
+    let mut old = std::mem::replace(last, Entry::new(new));
+    old.leave(ctx, after_leave)?;
+    Ok(Some(old.scene))
+}
+
+/// Something that happened to a stack while it was recording, see
+/// [`SceneStack::start_recording`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RecordedOp {
+    /// A transition was applied, during this frame
+    Transition { kind: TransitionKind, frame: u64 },
+    /// An event in this category was dispatched, during this frame
+    Event { category: EventMask, frame: u64 },
+}
+
+/// The order that the transitions queued by scenes in one frame are applied in, see
+/// [`SceneStack::set_transition_order`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TransitionOrder {
+    /// From the bottom of the stack up, as described on [`SceneStack`]
+    #[default]
+    AsCollected,
+    /// Pops (including `Clear` and `RemoveAt`) before everything else
+    PopsFirst,
+    /// Pushes (including `Insert`) before everything else
+    PushesFirst,
+}
+
+/// The order scenes are updated in each frame, see [`SceneStack::set_update_order`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UpdateOrder {
+    /// From the top of the stack down
+    #[default]
+    TopDown,
+    /// From the bottom of the stack up, so the scenes below are updated first
+    BottomUp,
+}
+
+/// Run a scene callback, turning a panic into [`SceneError::ScenePanicked`] if `catch` is set,
+/// and recording the scene's index in `panicked`.
+fn guard<T, E: From<SceneError>>(
+    catch: bool,
+    panicked: &mut Option<usize>,
+    depth: usize,
+    f: impl FnOnce() -> Result<T, E>,
+) -> Result<T, E> {
+    if !catch {
+        return f();
+    }
+    // Whatever the scene was doing is abandoned, and the scene removed, so unwind safety only
+    // matters for the context, see `SceneStack::set_catch_unwind`
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).unwrap_or_else(|_| {
+        *panicked = Some(depth);
+        Err(SceneError::ScenePanicked { depth }.into())
+    })
+}
+
+/// A description of one scene on the stack, see [`SceneStack::snapshot`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SceneInfo {
+    /// Where the scene is, with 0 at the bottom of the stack
+    pub index: usize,
+    pub name: String,
+    /// What the scene's `draw_next` returned
+    pub draws_next: bool,
+    /// Whether the scene is on top, so it's the only one that isn't paused
+    pub is_top: bool,
+}
+
+/// Lets a scene send a message to the scene below it, see [`Scene::on_parent`].
+///
+/// A message can be sent at any time, replacing any sent before it, and is held by the stack
+/// until the scene is popped. It's then passed to the uncovered scene's `on_unpause`, in the
+/// same frame and at the same point as [`Scene::result`] would be, which takes precedence if
+/// it returns anything. Messages sent after the scene has left the stack are dropped.
+#[derive(Debug, Clone)]
+pub struct ParentRef(Weak<RefCell<Option<Box<dyn Any>>>>);
+
+impl ParentRef {
+    pub fn send(&self, msg: impl Any) {
+        if let Some(slot) = self.0.upgrade() {
+            *slot.borrow_mut() = Some(Box::new(msg));
+        }
+    }
+}
+
+/// Identifies a scene for as long as it's on the stack, see [`SceneStack::remove`].
+///
+/// Every scene gets a new handle when it's pushed or inserted, unique across all stacks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SceneHandle(u64);
+
+impl SceneHandle {
+    fn next() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        Self(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// A scene on the stack, along with its handle.
+struct Entry<C, E, B: Backend> {
+    handle: SceneHandle,
+    scene: BoxScene<C, E, B>,
+    // The `should_draw` of the last `on_pause`, to pass back to `on_unpause_with`
+    was_drawn: bool,
+    // Entered, but not yet asked for `on_enter_transition`
+    fresh: bool,
+    // The scene this one replaced with a `Transition::Fade`, while it's still fading out
+    fade: Option<Fade<C, E, B>>,
+    // The last message sent through the scene's `ParentRef`
+    message: MessageSlot,
+}
+
+/// A scene that has left the stack, but is still being drawn under the one that replaced it,
+/// see [`Transition::Fade`].
+struct Fade<C, E, B: Backend> {
+    outgoing: BoxScene<C, E, B>,
+    elapsed: f64,
+    duration: f64,
+}
+
+impl<C, E, B: Backend> Fade<C, E, B> {
+    /// How far through the fade this is, from 0 to 1.
+    fn progress(&self) -> f64 {
+        if self.duration > 0.0 {
+            (self.elapsed / self.duration).min(1.0)
+        } else {
+            1.0
+        }
+    }
+}
+
+impl<C, E, B: Backend> Entry<C, E, B> {
+    fn new(scene: BoxScene<C, E, B>) -> Self {
+        let mut entry = Self {
+            handle: SceneHandle::next(),
+            scene,
+            was_drawn: true,
+            fresh: true,
+            fade: None,
+            message: MessageSlot::default(),
+        };
+        let parent = entry.parent_ref();
+        entry.scene.on_parent(parent);
+        entry
+    }
+
+    fn parent_ref(&self) -> ParentRef {
+        ParentRef(Rc::downgrade(&self.message))
+    }
+
+    /// What to pass to the scene below once this one has left: its `result`, or else the last
+    /// message sent through its `ParentRef`.
+    fn take_result(&mut self) -> Option<Box<dyn Any>> {
+        self.scene
+            .result()
+            .or_else(|| self.message.borrow_mut().take())
+    }
+
+    fn pause(&mut self, ctx: &mut C, should_draw: bool, reason: Option<&dyn Any>) -> Result<(), E> {
+        self.was_drawn = should_draw;
+        self.scene.pause(ctx, should_draw, reason)
+    }
+
+    fn unpause(&mut self, ctx: &mut C, result: Option<Box<dyn Any>>) -> Result<(), E> {
+        self.scene.unpause(ctx, result, self.was_drawn)
+    }
+
+    /// Leave the scene, adding its `on_leave_transition` to `after_leave` unless it's `None`.
+    fn leave(&mut self, ctx: &mut C, after_leave: &mut Vec<Transition<C, E, B>>) -> Result<(), E> {
+        self.scene.leave(ctx)?;
+        let trans = self.scene.on_leave_transition(ctx)?;
+        if !matches!(trans, Transition::None) {
+            after_leave.push(trans);
+        }
+        Ok(())
+    }
+}
+
+impl<C, E, B: Backend> std::ops::Deref for Entry<C, E, B> {
+    type Target = dyn Scene<E, B, Context = C>;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.scene
+    }
+}
+
+impl<C, E, B: Backend> std::ops::DerefMut for Entry<C, E, B> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut *self.scene
+    }
+}
+
+/// Drop the transitions in `ops` that cancel out, see [`SceneStack::set_coalesce`].
+fn coalesce<C, E, B: Backend>(ops: &mut Vec<Transition<C, E, B>>) {
+    let mut kept = 0;
+    for i in 0..ops.len() {
+        match ops[i] {
+            Transition::None => continue,
+            Transition::Pop
+                if kept > 0
+                    && matches!(
+                        ops[kept - 1],
+                        Transition::Push(_) | Transition::PushWith { .. }
+                    ) =>
+            {
+                kept -= 1;
+                continue;
+            }
+            _ => {}
+        }
+        // Everything from `kept` up to `i` has been dropped
+        ops.swap(kept, i);
+        kept += 1;
+    }
+    ops.truncate(kept);
+}
+
+/// Take the next seed from a SplitMix64 stream, so seeds that start close together still give
+/// unrelated results.
+pub(crate) fn split_seed(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// The seed for the next scene entered, if the stack has a seed.
+fn next_seed(seeds: &mut Option<u64>) -> Option<u64> {
+    seeds.as_mut().map(split_seed)
+}
+
+/// How long a chain of `on_enter_transition`s and `on_leave_transition`s, each following up the
+/// last, can get before the stack gives up. Scenes following up the same transition side by side
+/// don't add to it.
+const MAX_FOLLOW_UPS: usize = 64;
+
+/// Fail if adding `added` scenes to a stack of `len` would take it past `max_depth`.
+fn check_depth(len: usize, added: usize, max_depth: Option<usize>) -> Result<(), SceneError> {
+    match max_depth {
+        Some(max) if len + added > max => Err(SceneError::DepthExceeded { max }),
+        _ => Ok(()),
+    }
+}
+
+/// A stack of scenes, without a context of its own.
+///
+/// This does all of the work of a [`SceneManager`], but is handed the context on every call
+/// rather than owning it. That lets a stack live inside a scene, sharing the context of the
+/// stack that scene is on, by calling [`SceneStack::on_update_borrowed`] and friends from the
+/// scene's own callbacks. At the top level, use a [`SceneManager`], which owns its context and
+/// derefs to its stack.
+///
+/// A child stack can also run over just part of its parent's context, by passing that part to
+/// each call, or binding it once with [`SceneStack::with_context`].
+///
+/// Each frame, scenes are updated from the top of the stack down, for as long as each one wants
+/// the one below it drawn and isn't modal. The transitions they return are applied after all of
+/// them have been updated, from the bottom of the stack up, so that the transitions from the
+/// top scene are always applied last. For example, if the top scene pushes a new scene while a
+/// scene below it pops, the pop is applied first (removing the old top), and the new scene ends
+/// up on top. The transitions returned by a single scene are applied in the order it returned
+/// them. Events follow the same rule.
+///
+/// Unlike a [`SceneManager`], a stack can't call `on_leave` on its scenes when it's dropped, as
+/// it has no context to pass them.
+pub struct SceneStack<C, E = BoxError, B: Backend = SgeBackend> {
+    scenes: Vec<Entry<C, E, B>>,
+    operations: Vec<Transition<C, E, B>>,
+    fixed_timestep: Option<f64>,
+    // Time not yet consumed by fixed updates
+    accumulator: f64,
+    time_scale: f64,
+    // The `elapsed_time` of the last update, before scaling
+    unscaled_elapsed: f64,
+    on_transition: Option<TransitionObserver<C>>,
+    on_cursor: Option<CursorCallback<C>>,
+    // The cursor mode last passed to `on_cursor`
+    cursor: CursorMode,
+    paused: bool,
+    // Reset whenever a different scene comes to the top
+    time_in_scene: f64,
+    // Whether a scene has ever been on the stack
+    populated: bool,
+    max_depth: Option<usize>,
+    frame: u64,
+    order: TransitionOrder,
+    coalesce: bool,
+    draw_next_limit: Option<usize>,
+    focus: Option<usize>,
+    update_order: UpdateOrder,
+    // Time towards the next `on_tick` of each scene, by index
+    ticks: Vec<f64>,
+    catch_unwind: bool,
+    frame_budget: Option<f64>,
+    should_quit: bool,
+    // Update time each scene missed by going over the frame budget, by index
+    deferred: Vec<f64>,
+    // The `on_leave_transition`s still to apply, in the order the scenes left
+    after_leave: Vec<Transition<C, E, B>>,
+    seed: Option<u64>,
+    // Where the stream of seeds derived from `seed` has got to
+    seeds: Option<u64>,
+    // The index of a scene that panicked, waiting to be removed
+    panicked: Option<usize>,
+    recording: Option<Vec<RecordedOp>>,
+}
+
+impl<C, E: From<SceneError>, B: Backend> SceneStack<C, E, B> {
+    pub fn new(base_scene: BoxScene<C, E, B>) -> Self {
+        let mut stack = Self::empty();
+        // Never entered, so it doesn't get `on_enter_transition` either
+        let mut base = Entry::new(base_scene);
+        base.fresh = false;
+        stack.scenes.push(base);
+        stack.populated = true;
+        stack
+    }
+
+    fn empty() -> Self {
+        Self {
+            scenes: Vec::new(),
+            // Kept around to avoid allocating on every frame
+            operations: Vec::new(),
+            fixed_timestep: None,
+            accumulator: 0.0,
+            time_scale: 1.0,
+            unscaled_elapsed: 0.0,
+            on_transition: None,
+            on_cursor: None,
+            cursor: CursorMode::Inherit,
+            paused: false,
+            time_in_scene: 0.0,
+            populated: false,
+            max_depth: None,
+            frame: 0,
+            order: TransitionOrder::AsCollected,
+            coalesce: false,
+            draw_next_limit: None,
+            focus: None,
+            update_order: UpdateOrder::TopDown,
+            ticks: Vec::new(),
+            catch_unwind: false,
+            frame_budget: None,
+            should_quit: false,
+            deferred: Vec::new(),
+            after_leave: Vec::new(),
+            seed: None,
+            seeds: None,
+            panicked: None,
+            recording: None,
+        }
+    }
+
+    /// Borrow this stack along with a context, E.G. a field of a bigger one, so it can be
+    /// driven without passing the context every time.
+    pub fn with_context<'a>(&'a mut self, ctx: &'a mut C) -> BoundStack<'a, C, E, B> {
+        BoundStack::new(self, ctx)
+    }
+
+    /// Pause or resume the whole stack.
+    ///
+    /// While paused, no scene gets `on_update` or `on_fixed_update`, freezing the simulation,
+    /// and frame time isn't accumulated towards fixed updates. Events are still dispatched,
+    /// scenes are still drawn, and transitions queued from events are still applied, so menus
+    /// keep working. This is unrelated to `Scene::on_pause`, which is about a scene being
+    /// covered by another one, and doesn't trigger it.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Set a callback that's run after every transition is applied, whether it was queued by a
+    /// scene or applied directly (including through `push`, `pop` and `replace`).
+    ///
+    /// The callback only gets to observe the change, it can't apply transitions of its own.
+    /// `Transition::None` doesn't trigger it, and nor does a transition that leaves the stack
+    /// as it was, E.G. because a scene cancelled it in `on_before_leave`.
+    pub fn set_on_transition(&mut self, f: TransitionObserver<C>) {
+        self.on_transition = Some(f);
+    }
+
+    /// Record that the stack has changed, given how many scenes were on it beforehand and which
+    /// one was on top.
+    fn notify(
+        &mut self,
+        ctx: &mut C,
+        kind: TransitionKind,
+        old_len: usize,
+        old_top: Option<SceneHandle>,
+    ) {
+        self.populated |= !self.scenes.is_empty();
+        trace!(
+            ?kind,
+            depth = self.scenes.len(),
+            top = self.current_name(),
+            "applied transition"
+        );
+        let top_moved = self.current_handle() != old_top;
+        if kind.changes_top(old_len, self.scenes.len(), top_moved) {
+            self.time_in_scene = 0.0;
+        }
+        if kind == TransitionKind::None {
+            return;
+        }
+        if let Some(recording) = &mut self.recording {
+            recording.push(RecordedOp::Transition {
+                kind,
+                frame: self.frame,
+            });
+        }
+        // Scenes may have moved, so start every scene's ticks and deferred time over
+        self.ticks.clear();
+        self.deferred.clear();
+        self.apply_cursor(ctx);
+        if let Some(f) = &mut self.on_transition {
+            f(&kind, ctx);
+        }
+    }
+
+    /// Start recording every transition applied to the stack and every event dispatched to it,
+    /// E.G. to find out what led up to a crash. Only their kinds are kept, not the scenes.
+    ///
+    /// Anything recorded before is dropped.
+    pub fn start_recording(&mut self) {
+        self.recording = Some(Vec::new());
+    }
+
+    /// Stop recording, returning what was recorded since the last take.
+    pub fn stop_recording(&mut self) -> Vec<RecordedOp> {
+        self.recording.take().unwrap_or_default()
+    }
+
+    /// What's been recorded so far, oldest first, leaving the recording running but empty.
+    /// Returns an empty `Vec` if the stack isn't recording.
+    pub fn take_recording(&mut self) -> Vec<RecordedOp> {
+        self.recording
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    /// Set a callback that shows, hides or locks the cursor, E.G. through `sge`, whenever the
+    /// top scene's [`Scene::cursor`] asks for a change. It's first run from `on_create` for the
+    /// scene on top by then, and isn't run for [`CursorMode::Inherit`] or the mode that's
+    /// already applied.
+    pub fn set_on_cursor(&mut self, f: CursorCallback<C>) {
+        self.on_cursor = Some(f);
+    }
+
+    /// Pass the top scene's cursor mode to the cursor callback, if it's changed.
+    fn apply_cursor(&mut self, ctx: &mut C) {
+        let mode = self.current().map_or(CursorMode::Inherit, |s| s.cursor());
+        if mode == CursorMode::Inherit || mode == self.cursor {
+            return;
+        }
+        if let Some(f) = &mut self.on_cursor {
+            self.cursor = mode;
+            f(mode, ctx);
+        }
+    }
+
+    /// Limit how many scenes can be on the stack, or lift the limit with `None` (the default).
+    ///
+    /// Any push or insert that would go over the limit fails with
+    /// [`SceneError::DepthExceeded`] before the new scene is entered, guarding against scenes
+    /// pushing each other forever. Lowering the limit below the current depth doesn't remove
+    /// any scenes.
+    pub fn set_max_depth(&mut self, max: Option<usize>) {
+        self.max_depth = max;
+    }
+
+    pub fn max_depth(&self) -> Option<usize> {
+        self.max_depth
+    }
+
+    /// Change the order that the transitions queued by scenes each frame are applied in.
+    ///
+    /// This matters when scenes disagree, E.G. a scene pops while the one above it pushes: with
+    /// [`TransitionOrder::AsCollected`] (the default) or [`TransitionOrder::PopsFirst`] the pop
+    /// removes the old top and the new scene ends up on top, while with
+    /// [`TransitionOrder::PushesFirst`] the pop removes the new scene straight away. Otherwise,
+    /// transitions keep the order they were collected in. A [`Transition::If`] is sorted by the
+    /// branch it takes, so with either of these orders its condition is checked before any of
+    /// the frame's transitions are applied. Transitions applied directly, through `apply` and
+    /// friends, aren't affected.
+    pub fn set_transition_order(&mut self, order: TransitionOrder) {
+        self.order = order;
+    }
+
+    pub fn transition_order(&self) -> TransitionOrder {
+        self.order
+    }
+
+    /// Seed the stack, or stop seeding it with `None` (the default), so scenes can make random
+    /// choices that play out the same every run, E.G. for replays and tests.
+    ///
+    /// Every scene entered from then on gets [`Scene::on_seed`] with a seed of its own, derived
+    /// from this one, so each scene's stream is distinct, yet the same every time the same
+    /// transitions are applied. Setting the seed starts the derived seeds over, as does
+    /// [`SceneStack::reset`].
+    pub fn set_seed(&mut self, seed: Option<u64>) {
+        self.seed = seed;
+        self.seeds = seed;
+    }
+
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Split the next seed off the stack's stream, as a scene being entered would get, E.G. for
+    /// systems outside the stack. Returns `None` if the stack isn't seeded.
+    pub fn next_seed(&mut self) -> Option<u64> {
+        next_seed(&mut self.seeds)
+    }
+
+    /// Limit how many scenes below the top can be reached through `draw_next`, or lift the limit
+    /// with `None` (the default).
+    ///
+    /// Scenes past the limit aren't drawn or updated as usual, as if the last scene within it
+    /// had returned `false` from `draw_next`, and a warning is logged each frame that happens,
+    /// with the `tracing` feature. A whole stack of scenes drawing the ones below them is
+    /// usually a scene that was meant to be opaque, and walking all of it every frame is slow.
+    pub fn set_draw_next_limit(&mut self, limit: Option<usize>) {
+        self.draw_next_limit = limit;
+    }
+
+    pub fn draw_next_limit(&self) -> Option<usize> {
+        self.draw_next_limit
+    }
+
+    /// Give keyboard focus to the scene at index `depth`, where 0 is the bottom of the stack, E.G.
+    /// one panel of several that all draw the ones below them.
+    ///
+    /// Keyboard and text events go to the focused scene first, then, unless it consumes them,
+    /// down the stack from the top as usual, skipping the focused scene. Other events aren't
+    /// affected. If the stack shrinks, focus moves to the top scene, and it comes back if the
+    /// stack grows again. A modal scene above the focused one takes the focus back, so dialogs
+    /// still get the keyboard.
+    pub fn set_focus(&mut self, depth: usize) {
+        self.focus = Some(depth);
+    }
+
+    /// Send keyboard and text events to the top of the stack first again, as by default.
+    pub fn clear_focus(&mut self) {
+        self.focus = None;
+    }
+
+    /// The index of the scene that gets keyboard and text events first, if focus is set and no
+    /// modal scene above it has taken it back.
+    pub fn focus(&self) -> Option<usize> {
+        self.focused_index()
+    }
+
+    /// Drop transitions queued by scenes that would have no lasting effect, before applying
+    /// them each frame. This is off by default, as it changes which callbacks are called.
+    ///
+    /// After the transitions are put in order (see [`SceneStack::set_transition_order`]), every
+    /// `Transition::None` is dropped. Then every `Transition::Pop` that comes straight after a
+    /// `Transition::Push` or `Transition::PushWith` is dropped along with it, so the pushed
+    /// scene is never entered or left, and the scene below it is never paused or unpaused.
+    /// This repeats, so two pushes followed by two pops collapse too. Nothing else is touched,
+    /// and indices in a [`TransitionError`] count the transitions that are left.
+    pub fn set_coalesce(&mut self, coalesce: bool) {
+        self.coalesce = coalesce;
+    }
+
+    pub fn coalesce(&self) -> bool {
+        self.coalesce
+    }
+
+    /// Change the order scenes are updated in, E.G. so the world is updated before the HUD
+    /// over it reads from it. This covers both `on_update` and `on_fixed_update`.
+    ///
+    /// The same scenes are updated either way, but not quite at the same point:
+    /// [`UpdateOrder::TopDown`] (the default) checks each scene's `draw_next` and `is_modal`
+    /// just after updating it, while [`UpdateOrder::BottomUp`] has to check them all before
+    /// updating anything. The transitions the scenes return are applied in the same order
+    /// regardless.
+    pub fn set_update_order(&mut self, order: UpdateOrder) {
+        self.update_order = order;
+    }
+
+    pub fn update_order(&self) -> UpdateOrder {
+        self.update_order
+    }
+
+    /// Limit how long, in seconds of real time, updating the scenes may take each frame, or lift
+    /// the limit with `None` (the default).
+    ///
+    /// Before updating a scene that gives a [`Scene::update_budget`], the time already spent
+    /// this frame is checked, and if the scene's budget would take it over the limit, its
+    /// update and ticks are skipped. The time it missed is added to the `elapsed_time` of its
+    /// next update, unless a transition changes the stack first. Skipped scenes still get fixed
+    /// updates, events and draws as usual.
+    pub fn set_frame_budget(&mut self, budget: Option<f64>) {
+        self.frame_budget = budget;
+    }
+
+    pub fn frame_budget(&self) -> Option<f64> {
+        self.frame_budget
+    }
+
+    /// Catch panics in scenes instead of letting them unwind through the whole loop.
+    ///
+    /// When enabled, a panic in a scene's update, tick, event or draw callback is caught, the
+    /// scene is removed from the stack without calling `on_leave` (with the new top scene
+    /// getting `on_unpause` if it was on top), and the frame fails with
+    /// [`SceneError::ScenePanicked`]. The transitions other scenes queued earlier in the same
+    /// update or event are dropped, as they were meant for the stack as it was before the scene
+    /// was removed. Callbacks run while applying transitions aren't covered.
+    ///
+    /// The context may be left half-updated by the panicking scene, as it's passed on regardless
+    /// of unwind safety. Disabled by default.
+    pub fn set_catch_unwind(&mut self, catch: bool) {
+        self.catch_unwind = catch;
+    }
+
+    /// Move every [`Transition::Fade`] on by `elapsed_time`, dropping the outgoing scenes of those
+    /// that have finished.
+    fn advance_fades(&mut self, elapsed_time: f64) {
+        for entry in &mut self.scenes {
+            if let Some(fade) = &mut entry.fade {
+                fade.elapsed += elapsed_time;
+                if fade.elapsed >= fade.duration {
+                    entry.fade = None;
+                }
+            }
+        }
+    }
+
+    /// If a scene panicked, drop the transitions queued since there were `queued`, as they were
+    /// meant for the stack from before it was removed.
+    fn drop_panicked_operations(&mut self, queued: usize) {
+        if self.panicked.is_some() {
+            self.operations.truncate(queued);
+        }
+    }
+
+    /// Remove the scene that panicked, if any.
+    fn remove_panicked(&mut self, ctx: &mut C) -> Result<(), E> {
+        let len = self.scenes.len();
+        let old_top = self.current_handle();
+        match self.panicked.take() {
+            Some(depth) if depth < len => {
+                self.scenes.remove(depth);
+                if depth + 1 == len {
+                    if let Some(last) = self.scenes.last_mut() {
+                        last.unpause(ctx, None)?;
+                    }
+                }
+                self.notify(ctx, TransitionKind::RemoveAt(depth), len, old_top);
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Enable or disable fixed-timestep updates.
+    ///
+    /// With `Some(dt)`, frame time is accumulated and `on_fixed_update` is called once for each
+    /// whole `dt` that has elapsed, before the usual `on_update`. With `None` (the default), only
+    /// `on_update` is called.
+    ///
+    /// # Panics
+    ///
+    /// If `dt` isn't positive.
+    pub fn set_fixed_timestep(&mut self, dt: Option<f64>) {
+        if let Some(dt) = dt {
+            assert!(dt > 0.0, "Fixed timestep must be positive");
+        }
+        self.fixed_timestep = dt;
+        self.accumulator = 0.0;
+    }
+
+    pub fn fixed_timestep(&self) -> Option<f64> {
+        self.fixed_timestep
+    }
+
+    /// Scale the `elapsed_time` of every update before the scenes see it, E.G. 0.5 for slow
+    /// motion. This covers fixed updates, ticks and [`SceneStack::time_in_current_scene`] too.
+    /// A scale of 0 freezes the scenes like [`SceneStack::set_paused`], except that they're
+    /// still updated, with no time passing. The default is 1.
+    ///
+    /// # Panics
+    ///
+    /// If `scale` is negative or NaN.
+    pub fn set_time_scale(&mut self, scale: f64) {
+        assert!(scale >= 0.0, "Time scale can't be negative");
+        self.time_scale = scale;
+    }
+
+    pub fn time_scale(&self) -> f64 {
+        self.time_scale
+    }
+
+    /// The `elapsed_time` the last update was given, before the time scale was applied, for
+    /// anything that needs to follow the wall clock.
+    pub fn unscaled_elapsed_time(&self) -> f64 {
+        self.unscaled_elapsed
+    }
+
+    /// The number of scenes on the stack.
+    pub fn len(&self) -> usize {
+        self.scenes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scenes.is_empty()
+    }
+
+    /// How long the scene on top of the stack has been there, in the same units as
+    /// `elapsed_time`.
+    ///
+    /// This is reset whenever any transition puts a different scene on top, and doesn't
+    /// advance while the stack is paused.
+    pub fn time_in_current_scene(&self) -> f64 {
+        self.time_in_scene
+    }
+
+    /// How many frames have been run, counting every call to `on_update`, including while
+    /// paused. Unlike elapsed time, this is the same from run to run, for deterministic
+    /// scheduling.
+    pub fn current_frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// Whether a [`Transition::Quit`] has been applied, so the next update (or the current one,
+    /// if it was applied during an update) quits.
+    pub fn should_quit(&self) -> bool {
+        self.should_quit
+    }
+
+    /// Whether any scene has ever been on the stack.
+    ///
+    /// An empty stack quits either way, but this tells apart one whose scenes have all
+    /// finished from one that was never given a starting scene.
+    pub fn was_populated(&self) -> bool {
+        self.populated
+    }
+
+    /// The scene on top of the stack, if any.
+    pub fn current(&self) -> Option<&dyn Scene<E, B, Context = C>> {
+        self.scenes.last().map(|s| &**s)
+    }
+
+    pub fn current_mut(&mut self) -> Option<&mut dyn Scene<E, B, Context = C>> {
+        match self.scenes.last_mut() {
+            Some(s) => Some(&mut **s),
+            None => None,
+        }
+    }
+
+    /// Iterate over the scenes on the stack, from the bottom (index 0, the oldest scene) to the
+    /// top.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &dyn Scene<E, B, Context = C>> + '_ {
+        self.scenes.iter().map(|s| &**s)
+    }
+
+    pub fn iter_mut(
+        &mut self,
+    ) -> impl DoubleEndedIterator<Item = &mut (dyn Scene<E, B, Context = C> + 'static)> + '_ {
+        self.scenes.iter_mut().map(|s| &mut **s)
+    }
+
+    /// The name and saved state of each scene, from the bottom of the stack to the top.
+    ///
+    /// Scenes can't be deserialized directly, so restoring the stack is up to you, E.G. by
+    /// mapping each name back to a function that builds the scene from its state.
+    #[cfg(feature = "serde")]
+    pub fn serialize_stack(&self) -> Vec<(String, Option<serde_json::Value>)> {
+        self.scenes
+            .iter()
+            .map(|s| (s.name().to_owned(), s.save_state()))
+            .collect()
+    }
+
+    /// The name of the scene on top of the stack, if any.
+    pub fn current_name(&self) -> Option<&str> {
+        self.current().map(|s| s.name())
+    }
+
+    /// Whether any scene on the stack is called `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.find_by_name(name).is_some()
+    }
+
+    /// The scene called `name` closest to the top of the stack, if any.
+    pub fn find_by_name(&self, name: &str) -> Option<&dyn Scene<E, B, Context = C>> {
+        self.iter().rev().find(|s| s.name() == name)
+    }
+
+    /// Describe every scene on the stack, from the bottom up, E.G. for a debug overlay. This
+    /// calls `draw_next` once on each scene.
+    pub fn snapshot(&self, ctx: &C) -> Vec<SceneInfo> {
+        let len = self.scenes.len();
+        self.iter()
+            .enumerate()
+            .map(|(index, s)| SceneInfo {
+                index,
+                name: s.name().to_owned(),
+                draws_next: s.draw_next(ctx),
+                is_top: index + 1 == len,
+            })
+            .collect()
+    }
+
+    /// Apply a transition straight away, calling the same scene callbacks as if a scene had
+    /// returned it, then running the transition callback.
+    ///
+    /// This is the building block for driving a stack from a loop of your own, or from a test,
+    /// instead of through [`sge::Application`]: along with [`SceneStack::on_update_borrowed`]
+    /// and [`SceneStack::dispatch_event`], it covers everything the manager does with
+    /// transitions.
+    pub fn apply(&mut self, ctx: &mut C, trans: Transition<C, E, B>) -> Result<(), E> {
+        self.apply_without_follow_up(ctx, trans)?;
+        self.follow_up(ctx)
+    }
+
+    /// Apply a transition, without applying the transitions the scenes it entered or left
+    /// follow it up with.
+    fn apply_without_follow_up(
+        &mut self,
+        ctx: &mut C,
+        trans: Transition<C, E, B>,
+    ) -> Result<(), E> {
+        // Resolved first, so the branch that runs is what gets observed and recorded
+        let trans = trans.resolve(ctx);
+        let kind = trans.kind();
+        let old_len = self.scenes.len();
+        let old_top = self.current_handle();
+        let changed = trans.apply_to(
+            &mut self.scenes,
+            ctx,
+            self.max_depth,
+            &mut self.should_quit,
+            &mut self.after_leave,
+            &mut self.seeds,
+        )?;
+        // A transition a scene cancelled, or that had nothing to do, isn't reported
+        if changed {
+            self.notify(ctx, kind, old_len, old_top);
+        }
+        Ok(())
+    }
+
+    /// Apply the `on_leave_transition`s of the scenes that have left since the last call, in
+    /// the order they left, then the `on_enter_transition` of every scene that's been entered,
+    /// from the bottom of the stack up, then those of any scenes they left or entered, and so
+    /// on.
+    fn follow_up(&mut self, ctx: &mut C) -> Result<(), E> {
+        // How many follow-ups led to each pending one, by its place in `after_leave` or the
+        // handle of the scene that was entered. Anything not in them was caused by the
+        // transition that started this, so it's the first in its chain
+        let mut leave_depths: VecDeque<usize> = self.after_leave.iter().map(|_| 1).collect();
+        let mut enter_depths = HashMap::new();
+        loop {
+            let (trans, depth) = if !self.after_leave.is_empty() {
+                let depth = leave_depths.pop_front().unwrap_or(1);
+                (self.after_leave.remove(0), depth)
+            } else if let Some(entry) = self.scenes.iter_mut().find(|e| e.fresh) {
+                entry.fresh = false;
+                let depth = enter_depths.remove(&entry.handle).unwrap_or(1);
+                (entry.on_enter_transition(ctx)?, depth)
+            } else {
+                return Ok(());
+            };
+            if matches!(trans, Transition::None) {
+                continue;
+            }
+            if depth > MAX_FOLLOW_UPS {
+                return Err(SceneError::RedirectLoop {
+                    max: MAX_FOLLOW_UPS,
+                }
+                .into());
+            }
+            self.apply_without_follow_up(ctx, trans)?;
+            // Whatever that left or entered follows on from it
+            leave_depths.resize(self.after_leave.len(), depth + 1);
+            for entry in self.scenes.iter().filter(|e| e.fresh) {
+                enter_depths.entry(entry.handle).or_insert(depth + 1);
+            }
+        }
+    }
+
+    /// Whether a transition of this kind would change the stack as it is now, E.G. to grey out
+    /// a back button when there's nothing to pop to.
+    ///
+    /// This can only go by the kind, so it can't know whether a scene would cancel its own
+    /// removal in `on_before_leave`, where a `PopTo` or `PopWhile` would stop, or which branch an
+    /// `If` would take. Those count as changing the stack whenever they could.
+    pub fn can_apply(&self, kind: &TransitionKind) -> bool {
+        let len = self.scenes.len();
+        let fits = |added| check_depth(len, added, self.max_depth).is_ok();
+        match *kind {
+            TransitionKind::None => false,
+            TransitionKind::Push | TransitionKind::PushWith | TransitionKind::PushFn => fits(1),
+            TransitionKind::Insert { .. } => fits(1),
+            TransitionKind::PushAll(n) => n > 0 && fits(n),
+            TransitionKind::PopN(n) => n > 0 && len > 0,
+            TransitionKind::Pop
+            | TransitionKind::PopTo
+            | TransitionKind::PopWhile
+            | TransitionKind::Replace
+            | TransitionKind::Fade
+            | TransitionKind::Clear
+            | TransitionKind::Map
+            | TransitionKind::Crossfade
+            | TransitionKind::Restart => len > 0,
+            TransitionKind::Quit => !self.should_quit,
+            TransitionKind::Swap => len >= 2,
+            TransitionKind::RemoveAt(index) => index < len,
+            TransitionKind::ReplaceAt { depth } => depth < len,
+            TransitionKind::ReplaceAll | TransitionKind::If => true,
+            TransitionKind::Sequence(n) => n > 0,
+        }
+    }
+
+    /// Like `apply`, but returns how many scenes are on the stack afterwards.
+    pub fn apply_reporting(&mut self, ctx: &mut C, trans: Transition<C, E, B>) -> Result<usize, E> {
+        self.apply(ctx, trans)?;
+        Ok(self.scenes.len())
+    }
+
+    /// Send a message to the scene at index `depth`, where 0 is the bottom of the stack,
+    /// returning whether it handled it. Returns `Ok(false)` if there's no scene at `depth`.
+    pub fn send_to<M: Any>(&mut self, ctx: &mut C, depth: usize, msg: M) -> Result<bool, E> {
+        match self.scenes.get_mut(depth) {
+            Some(scene) => scene.on_message(ctx, &msg),
+            None => Ok(false),
+        }
+    }
+
+    /// Insert a scene at index `depth`, as with [`Transition::Insert`].
+    pub fn insert_at(
+        &mut self,
+        ctx: &mut C,
+        depth: usize,
+        scene: BoxScene<C, E, B>,
+    ) -> Result<(), E> {
+        self.apply(ctx, Transition::Insert { depth, scene })
+    }
+
+    /// Apply several transitions in order.
+    ///
+    /// If one fails, the error reports its index, and the transitions after it are queued to be
+    /// applied at the end of the next update or event rather than being dropped.
+    pub fn apply_all(
+        &mut self,
+        ctx: &mut C,
+        transitions: impl IntoIterator<Item = Transition<C, E, B>>,
+    ) -> Result<(), TransitionError<E>> {
+        let mut transitions = transitions.into_iter().enumerate();
+        for (index, trans) in transitions.by_ref() {
+            if let Err(source) = self.apply(ctx, trans) {
+                self.operations.extend(transitions.map(|(_, t)| t));
+                return Err(TransitionError { index, source });
+            }
+        }
+        Ok(())
+    }
+
+    /// The index of the lowest scene that should be drawn, found by walking down from the top
+    /// while each scene wants the one below it drawn.
+    fn visible_start(&self, ctx: &C) -> usize {
+        let mut start = self.scenes.len();
+        for scene in self.scenes.iter().rev() {
+            start -= 1;
+            if !scene.draw_next(ctx) || self.at_draw_next_limit(start) {
+                break;
+            }
+        }
+        start
+    }
+
+    /// Whether the scene at `index` is as far below the top as `draw_next` is allowed to reach.
+    fn at_draw_next_limit(&self, index: usize) -> bool {
+        let len = self.scenes.len();
+        self.draw_next_limit.is_some_and(|l| len - 1 - index >= l)
+    }
+
+    /// How many scenes, counting down from the top, are drawn each frame.
+    ///
+    /// This is the top scene plus every scene below it reached through `draw_next`.
+    pub fn visible_depth(&self, ctx: &C) -> usize {
+        self.scenes.len() - self.visible_start(ctx)
+    }
+
+    /// Whether the top scene wants the one below it drawn, E.G. to skip clearing the screen when
+    /// an opaque scene covers everything. Returns `false` if the stack is empty.
+    pub fn top_draws_next(&self, ctx: &C) -> bool {
+        self.current().is_some_and(|s| s.draw_next(ctx))
+    }
+
+    /// Draw the visible scenes from the bottom up, so each is drawn over the ones beneath it.
+    fn draw(&mut self, ctx: &mut C) -> Result<(), E> {
+        let start = self.visible_start(ctx);
+        if start > 0 && self.at_draw_next_limit(start) && self.scenes[start].draw_next(ctx) {
+            warn!(
+                limit = ?self.draw_next_limit,
+                depth = self.scenes.len(),
+                "draw_next reached its limit, so the scenes further down weren't drawn"
+            );
+        }
+        for (i, entry) in self.scenes[start..].iter_mut().enumerate() {
+            guard(self.catch_unwind, &mut self.panicked, start + i, || {
+                let Some(fade) = &mut entry.fade else {
+                    return entry.scene.on_draw_with_alpha(ctx, 1.0);
+                };
+                let t = fade.progress();
+                fade.outgoing.on_draw_with_alpha(ctx, 1.0 - t)?;
+                entry.scene.on_draw_with_alpha(ctx, t)
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Run fixed and variable updates on the scenes that should see them, queuing any
+    /// transitions they return.
+    fn update_scenes(&mut self, ctx: &mut C, elapsed_time: f64) -> Result<(), E> {
+        let started = Instant::now();
+        let len = self.scenes.len();
+        let order = self.update_order;
+        // Bottom-up updates have to know where to start before updating anything
+        let start = match order {
+            UpdateOrder::TopDown => 0,
+            UpdateOrder::BottomUp => self.update_start(ctx),
+        };
+        let index_of = |i| match order {
+            UpdateOrder::TopDown => len - 1 - i,
+            UpdateOrder::BottomUp => i,
+        };
+        let limit = self.draw_next_limit;
+        let at_limit = move |index: usize| limit.is_some_and(|l| len - 1 - index >= l);
+        if let Some(dt) = self.fixed_timestep {
+            self.accumulator += elapsed_time;
+            while self.accumulator >= dt {
+                self.accumulator -= dt;
+                let mut blocked = false;
+                for i in 0..len {
+                    let index = index_of(i);
+                    let scene = &mut self.scenes[index];
+                    if (blocked || index < start) && !scene.always_update() {
+                        continue;
+                    }
+                    guard(self.catch_unwind, &mut self.panicked, index, || {
+                        scene.on_fixed_update(ctx, dt)
+                    })?;
+                    if order == UpdateOrder::TopDown {
+                        blocked |= scene.is_modal() || !scene.draw_next(ctx) || at_limit(index);
+                    }
+                }
+            }
+        }
+        self.ticks.resize(len, 0.0);
+        self.deferred.resize(len, 0.0);
+        let frame_start = self.operations.len();
+        // Once set, only scenes that always update are updated
+        let mut blocked = false;
+        for i in 0..len {
+            let index = index_of(i);
+            // Split so the scene can see the one below it
+            let (below, rest) = self.scenes.split_at_mut(index);
+            let scene = &mut rest[0];
+            if (blocked || index < start) && !scene.always_update() {
+                continue;
+            }
+            let over_budget = self
+                .frame_budget
+                .zip(scene.update_budget())
+                .is_some_and(|(budget, cost)| started.elapsed().as_secs_f64() + cost > budget);
+            if over_budget {
+                // Catch up on the time this scene missed in a later frame
+                self.deferred[index] += elapsed_time;
+                if order == UpdateOrder::TopDown {
+                    blocked |= scene.is_modal() || !scene.draw_next(ctx) || at_limit(index);
+                }
+                continue;
+            }
+            let elapsed_time = elapsed_time + std::mem::take(&mut self.deferred[index]);
+            if let Some(interval) = scene.tick_interval().filter(|&i| i > 0.0) {
+                let ticks = &mut self.ticks[index];
+                *ticks += elapsed_time;
+                while *ticks >= interval {
+                    *ticks -= interval;
+                    guard(self.catch_unwind, &mut self.panicked, index, || {
+                        scene.on_tick(ctx)
+                    })?;
+                }
+            }
+            let before = self.operations.len();
+            let res = if scene.wants_below() {
+                let below = below.last().map(|s| &**s);
+                guard(self.catch_unwind, &mut self.panicked, index, || {
+                    scene.on_update_with_below(ctx, elapsed_time, below)
+                })
+                .map(|trans| self.operations.push(trans))
+            } else {
+                guard(self.catch_unwind, &mut self.panicked, index, || {
+                    scene.on_update_many(ctx, elapsed_time, &mut self.operations)
+                })
+            };
+            let res = res.and_then(|()| {
+                let poll = guard(self.catch_unwind, &mut self.panicked, index, || {
+                    scene.poll_ready(ctx)
+                })?;
+                if let Poll::Ready(trans) = poll {
+                    self.operations.push(trans);
+                }
+                Ok(())
+            });
+            if let Err(err) = res {
+                // A scene that panicked is removed rather than given a chance to recover
+                if self.panicked.is_some() {
+                    return Err(err);
+                }
+                let trans = scene.on_error(ctx, err)?;
+                self.operations.push(trans);
+            }
+            if order == UpdateOrder::TopDown {
+                // Move this scene's transitions ahead of those from the scenes above it
+                let added = self.operations.len() - before;
+                self.operations[frame_start..].rotate_right(added);
+                blocked |= scene.is_modal() || !scene.draw_next(ctx) || at_limit(index);
+            }
+        }
+        Ok(())
+    }
+
+    /// The index of the lowest scene that's updated as usual, found by walking down from the
+    /// top while each scene wants the one below it drawn and isn't modal.
+    fn update_start(&self, ctx: &C) -> usize {
+        let mut start = self.scenes.len();
+        for scene in self.scenes.iter().rev() {
+            start -= 1;
+            if scene.is_modal() || !scene.draw_next(ctx) || self.at_draw_next_limit(start) {
+                break;
+            }
+        }
+        start
+    }
+
+    /// Pass window changes that every scene needs to know about to all of them.
+    fn dispatch_window_change(&mut self, ctx: &mut C, change: WindowChange) -> Result<(), E> {
+        for scene in &mut self.scenes {
+            match change {
+                WindowChange::Resized { width, height } => scene.on_resize(ctx, width, height)?,
+                WindowChange::FocusLost => scene.on_background(ctx)?,
+                WindowChange::FocusGained => scene.on_foreground(ctx)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// How many transitions are queued, waiting to be applied.
+    pub fn pending_operations(&self) -> usize {
+        self.operations.len()
+    }
+
+    /// How many transitions can be queued before the queue has to allocate more room.
+    pub fn operation_capacity(&self) -> usize {
+        self.operations.capacity()
+    }
+
+    /// Queue a transition without applying it, as if a scene had returned it. It's applied
+    /// along with the scenes' transitions at the end of the next update or event, or by
+    /// [`SceneStack::flush_operations`].
+    pub fn queue(&mut self, trans: Transition<C, E, B>) {
+        self.operations.push(trans);
+    }
+
+    /// Apply every queued transition now, in the order set by
+    /// [`SceneStack::set_transition_order`].
+    pub fn flush_operations(&mut self, ctx: &mut C) -> Result<(), TransitionError<E>> {
+        self.apply_operations(ctx)
+    }
+
+    /// Apply the transitions queued by scenes this frame.
+    fn apply_operations(&mut self, ctx: &mut C) -> Result<(), TransitionError<E>> {
+        // Taken so that anything left over after a failure can be queued again, while keeping
+        // the allocation around
+        let mut ops = std::mem::take(&mut self.operations);
+        // Sorting is stable, so anything not moved forward keeps its place
+        if self.order != TransitionOrder::AsCollected {
+            // Sorted by the branch each condition takes
+            for trans in &mut ops {
+                *trans = std::mem::take(trans).resolve(ctx);
+            }
+        }
+        match self.order {
+            TransitionOrder::AsCollected => {}
+            TransitionOrder::PopsFirst => ops.sort_by_key(|t| !t.kind().is_pop()),
+            TransitionOrder::PushesFirst => ops.sort_by_key(|t| !t.kind().is_push()),
+        }
+        if self.coalesce {
+            coalesce(&mut ops);
+        }
+        let res = self.apply_all(ctx, ops.drain(..));
+        ops.append(&mut self.operations);
+        self.operations = ops;
+        res
+    }
+
+    /// Enter and push a scene, returning a handle that can later be passed to
+    /// [`SceneStack::remove`].
+    pub fn push(&mut self, ctx: &mut C, mut new: BoxScene<C, E, B>) -> Result<SceneHandle, E> {
+        check_depth(self.scenes.len(), 1, self.max_depth)?;
+        new.enter(ctx, next_seed(&mut self.seeds))?;
+        let entry = Entry::new(new);
+        let handle = entry.handle;
+        let old_top = self.current_handle();
+        self.scenes.push(entry);
+        self.notify(ctx, TransitionKind::Push, self.scenes.len() - 1, old_top);
+        self.follow_up(ctx)?;
+        Ok(handle)
+    }
+
+    pub fn pop(&mut self, ctx: &mut C) -> Result<Option<BoxScene<C, E, B>>, E> {
+        let mut old = self.scenes.pop();
+        if let Some(ref mut old) = old {
+            old.leave(ctx, &mut self.after_leave)?;
+            self.notify(
+                ctx,
+                TransitionKind::Pop,
+                self.scenes.len() + 1,
+                Some(old.handle),
+            );
+            self.follow_up(ctx)?;
+        }
+        Ok(old.map(|e| e.scene))
+    }
+
+    /// The handle of the scene at index `depth`, where 0 is the bottom of the stack.
+    pub fn handle_at(&self, depth: usize) -> Option<SceneHandle> {
+        self.scenes.get(depth).map(|e| e.handle)
+    }
+
+    /// The handle of the scene on top of the stack, E.G. to keep after applying a push.
+    pub fn current_handle(&self) -> Option<SceneHandle> {
+        self.scenes.last().map(|e| e.handle)
+    }
+
+    /// The index of the scene with this handle, if it's still on the stack.
+    pub fn depth_of(&self, handle: SceneHandle) -> Option<usize> {
+        self.scenes.iter().position(|e| e.handle == handle)
+    }
+
+    /// Remove the scene with this handle straight away, wherever it is on the stack, calling
+    /// its `on_leave`. If it was on top, the scene left on top gets `on_unpause` with its
+    /// [`Scene::result`].
+    ///
+    /// Returns `Ok(None)` if the scene has already left the stack.
+    pub fn remove(
+        &mut self,
+        ctx: &mut C,
+        handle: SceneHandle,
+    ) -> Result<Option<BoxScene<C, E, B>>, E> {
+        let Some(depth) = self.depth_of(handle) else {
+            return Ok(None);
+        };
+        let len = self.scenes.len();
+        let old_top = self.current_handle();
+        let mut old = self.scenes.remove(depth);
+        old.leave(ctx, &mut self.after_leave)?;
+        if depth + 1 == len {
+            let result = old.take_result();
+            if let Some(last) = self.scenes.last_mut() {
+                last.unpause(ctx, result)?;
+            }
+        }
+        self.notify(ctx, TransitionKind::RemoveAt(depth), len, old_top);
+        self.follow_up(ctx)?;
+        Ok(Some(old.scene))
+    }
+
+    /// Pop every scene straight away, calling `on_leave` on each from top to bottom.
+    ///
+    /// Does nothing if the stack is already empty.
+    pub fn clear(&mut self, ctx: &mut C) -> Result<(), E> {
+        if self.leave_all(ctx)? {
+            self.follow_up(ctx)?;
+        }
+        Ok(())
+    }
+
+    /// Pop and leave every scene, returning whether there were any.
+    fn leave_all(&mut self, ctx: &mut C) -> Result<bool, E> {
+        let old_len = self.scenes.len();
+        if old_len == 0 {
+            return Ok(false);
+        }
+        let old_top = self.current_handle();
+        while let Some(mut old) = self.scenes.pop() {
+            old.leave(ctx, &mut self.after_leave)?;
+        }
+        self.notify(ctx, TransitionKind::Clear, old_len, old_top);
+        Ok(true)
+    }
+
+    /// Return the stack to how it was when it was created, so it can be reused.
+    ///
+    /// Every scene is popped straight away, calling `on_leave` on each from top to bottom, and
+    /// any transitions still queued are dropped, along with the scenes'
+    /// `on_leave_transition`s. The frame count, the time in the current scene
+    /// and the paused state are reset too, while settings like the fixed timestep and the
+    /// maximum depth are kept.
+    pub fn reset(&mut self, ctx: &mut C) -> Result<(), E> {
+        // Cleared first, so they're dropped even if a scene fails to leave
+        self.operations.clear();
+        let res = self.leave_all(ctx);
+        self.after_leave.clear();
+        res?;
+        self.ticks.clear();
+        self.deferred.clear();
+        self.accumulator = 0.0;
+        self.paused = false;
+        self.time_in_scene = 0.0;
+        self.populated = false;
+        self.should_quit = false;
+        self.focus = None;
+        self.seeds = self.seed;
+        self.frame = 0;
+        Ok(())
+    }
+
+    /// Replace the top scene, returning the old one.
+    ///
+    /// Fails with [`SceneError::EmptyStack`] if there's no scene to replace.
+    pub fn replace(
+        &mut self,
+        ctx: &mut C,
+        mut new: BoxScene<C, E, B>,
+    ) -> Result<BoxScene<C, E, B>, E> {
+        let last = self.scenes.last_mut().ok_or(SceneError::EmptyStack)?;
+        new.enter(ctx, next_seed(&mut self.seeds))?;
+        let mut old = std::mem::replace(last, Entry::new(new));
+        old.leave(ctx, &mut self.after_leave)?;
+        self.notify(
+            ctx,
+            TransitionKind::Replace,
+            self.scenes.len(),
+            Some(old.handle),
+        );
+        self.follow_up(ctx)?;
+        Ok(old.scene)
+    }
+
+    /// Replace the scene at index `depth`, where 0 is the bottom of the stack, returning the old
+    /// one.
+    ///
+    /// Replacing the top scene pauses it first, just like [`Transition::Replace`]. Replacing a
+    /// scene below the top leaves the top scene alone. Fails with [`SceneError::OutOfRange`] if
+    /// there's no scene at `depth`.
+    pub fn replace_at(
+        &mut self,
+        ctx: &mut C,
+        depth: usize,
+        mut new: BoxScene<C, E, B>,
+    ) -> Result<BoxScene<C, E, B>, E> {
+        let len = self.scenes.len();
+        let old_top = self.current_handle();
+        let slot = self
+            .scenes
+            .get_mut(depth)
+            .ok_or(SceneError::OutOfRange { index: depth, len })?;
+        let is_top = depth + 1 == len;
+        if is_top {
+            let draw_next = new.draw_next(ctx);
+            slot.pause(ctx, draw_next, new.pause_reason())?;
+        }
+        new.enter(ctx, next_seed(&mut self.seeds))?;
+        let mut old = std::mem::replace(slot, Entry::new(new));
+        old.leave(ctx, &mut self.after_leave)?;
+        let kind = if is_top {
+            TransitionKind::Replace
+        } else {
+            TransitionKind::ReplaceAt { depth }
+        };
+        self.notify(ctx, kind, len, old_top);
+        self.follow_up(ctx)?;
+        Ok(old.scene)
+    }
+
+    /// Replace every scene below the top with `new`, E.G. to change levels under a console
+    /// overlay.
+    ///
+    /// The scenes below the top get `on_leave`, from the top down, then `new` gets `on_enter`
+    /// and goes on the bottom. The top scene gets no callbacks at all. With only one scene on the
+    /// stack, this just inserts `new` beneath it, failing with [`SceneError::DepthExceeded`]
+    /// first if that would take the stack past its maximum depth. Fails with
+    /// [`SceneError::EmptyStack`] if there's no top scene to keep.
+    pub fn replace_below_top(&mut self, ctx: &mut C, mut new: BoxScene<C, E, B>) -> Result<(), E> {
+        let len = self.scenes.len();
+        if len == 0 {
+            return Err(SceneError::EmptyStack.into());
+        }
+        if len == 1 {
+            // Nothing is removed, so the stack grows
+            check_depth(len, 1, self.max_depth)?;
+        }
+        let old_top = self.current_handle();
+        for depth in (0..len - 1).rev() {
+            self.scenes
+                .remove(depth)
+                .leave(ctx, &mut self.after_leave)?;
+        }
+        new.enter(ctx, next_seed(&mut self.seeds))?;
+        self.scenes.insert(0, Entry::new(new));
+        let kind = if len > 1 {
+            TransitionKind::ReplaceAt { depth: 0 }
+        } else {
+            TransitionKind::Insert { depth: 0 }
+        };
+        self.notify(ctx, kind, len, old_top);
+        self.follow_up(ctx)
+    }
+
+    /// Panic if the stack's internal state is inconsistent, to catch bugs in transition logic
+    /// from tests. Call it between frames, as transitions queued mid-frame are expected.
+    ///
+    /// Checks that no two scenes share a handle, that no transitions are still queued or waiting
+    /// to follow up a scene leaving, that no panicked scene was left on the stack, that the
+    /// depth is within the maximum, and that per-scene timers line up with the scenes.
+    #[cfg(any(test, feature = "test-util"))]
+    pub fn debug_assert_invariants(&self) {
+        let len = self.scenes.len();
+        for (i, entry) in self.scenes.iter().enumerate() {
+            if let Some(j) = self.scenes[i + 1..]
+                .iter()
+                .position(|e| e.handle == entry.handle)
+            {
+                panic!(
+                    "Scenes at depths {i} and {} share the handle {:?}",
+                    i + 1 + j,
+                    entry.handle
+                );
+            }
+        }
+        assert!(
+            self.operations.is_empty(),
+            "{} transitions are still queued between frames: {:?}",
+            self.operations.len(),
+            self.operations
+        );
+        if let Some(depth) = self.panicked {
+            panic!("The scene that panicked at depth {depth} wasn't removed");
+        }
+        assert!(
+            self.after_leave.is_empty(),
+            "{} on_leave_transitions were never applied",
+            self.after_leave.len()
+        );
+        if let Some(max) = self.max_depth {
+            assert!(len <= max, "Depth {len} exceeds the maximum of {max}");
+        }
+        for (name, timers) in [("tick", &self.ticks), ("deferred time", &self.deferred)] {
+            assert!(
+                timers.is_empty() || timers.len() == len,
+                "There are {} {name} timers for {len} scenes",
+                timers.len()
+            );
+        }
+    }
+
+    /// Run `on_create` on every scene, returning `false` if any of them did, or if the stack is
+    /// empty.
+    pub fn on_create_borrowed(&mut self, ctx: &mut C) -> Result<bool, E> {
+        for scene in &mut self.scenes {
+            if !scene.on_create(ctx)? {
+                return Ok(false);
+            }
+        }
+        self.apply_cursor(ctx);
+        // If there are no scenes, quit
+        Ok(!self.scenes.is_empty() && !self.should_quit)
+    }
+
+    /// Run one frame: update the scenes, apply the transitions they return, then draw them.
+    /// Returns `false` once the stack is empty.
+    pub fn on_update_borrowed(&mut self, ctx: &mut C, elapsed_time: f64) -> Result<bool, E> {
+        self.step(ctx, elapsed_time)?;
+        let res = self.draw(ctx);
+        self.remove_panicked(ctx)?;
+        res?;
+        self.frame += 1;
+        // If there are no more scenes, quit
+        Ok(!self.scenes.is_empty() && !self.should_quit)
+    }
+
+    /// Update the scenes and apply the transitions they return, without drawing, E.G. to run
+    /// several sub-steps per frame where each sees the changes the last one made. Returns
+    /// `false` once the stack is empty.
+    ///
+    /// [`SceneStack::on_update_borrowed`] is one step followed by a draw. Steps don't count as
+    /// frames, so [`SceneStack::current_frame`] only advances once the frame is drawn.
+    pub fn step(&mut self, ctx: &mut C, elapsed_time: f64) -> Result<bool, E> {
+        self.unscaled_elapsed = elapsed_time;
+        let elapsed_time = elapsed_time * self.time_scale;
+        if !self.paused {
+            self.time_in_scene += elapsed_time;
+            self.advance_fades(elapsed_time);
+            let queued = self.operations.len();
+            let res = self.update_scenes(ctx, elapsed_time);
+            self.drop_panicked_operations(queued);
+            self.remove_panicked(ctx)?;
+            res?;
+        }
+        self.apply_operations(ctx).map_err(|e| e.source)?;
+        Ok(!self.scenes.is_empty() && !self.should_quit)
+    }
+
+    /// Dispatch an event down the stack, then apply the transitions the scenes return. Returns
+    /// whether any scene handled it.
+    pub fn on_event_borrowed(&mut self, ctx: &mut C, event: &B::Event) -> Result<bool, E> {
+        Ok(self.dispatch_event(ctx, event)?.handled())
+    }
+
+    /// Like [`SceneStack::on_event_borrowed`], but tells apart an event that was consumed from
+    /// one that was only observed, E.G. to decide whether to pass it on to systems outside the
+    /// stack.
+    pub fn dispatch_event(&mut self, ctx: &mut C, event: &B::Event) -> Result<EventOutcome, E> {
+        if let Some(recording) = &mut self.recording {
+            recording.push(RecordedOp::Event {
+                category: B::event_category(event),
+                frame: self.frame,
+            });
+        }
+        if let Some(change) = B::window_change(event) {
+            self.dispatch_window_change(ctx, change)?;
+        }
+        let queued = self.operations.len();
+        let res = self.event_scenes(ctx, event);
+        self.drop_panicked_operations(queued);
+        self.remove_panicked(ctx)?;
+        let outcome = res?;
+        self.apply_operations(ctx).map_err(|e| e.source)?;
+        Ok(outcome)
+    }
+
+    /// Pass an event down the stack to the scenes that should see it, queuing any
+    /// transitions they return.
+    fn event_scenes(&mut self, ctx: &mut C, event: &B::Event) -> Result<EventOutcome, E> {
+        let mut outcome = EventOutcome::Ignored;
+        let event_start = self.operations.len();
+        let category = B::event_category(event);
+        let focused = self
+            .focused_index()
+            .filter(|_| category.intersects(EventMask::KEYBOARD | EventMask::TEXT));
+        // The focused scene first, then the rest from the top down
+        let order = focused.into_iter().chain(
+            (0..self.scenes.len())
+                .rev()
+                .filter(move |&i| Some(i) != focused),
+        );
+        for index in order {
+            let is_focused = Some(index) == focused;
+            let scene = &mut self.scenes[index];
+            if !scene.event_mask().intersects(category) {
+                if !is_focused && (scene.is_modal() || !scene.passes_events_below(ctx)) {
+                    break;
+                }
+                continue;
+            }
+            let res = guard(self.catch_unwind, &mut self.panicked, index, || {
+                scene.on_event_flow(ctx, event)
+            });
+            let (flow, trans) = match res {
+                Ok(res) => res,
+                Err(err) if self.panicked.is_none() => {
+                    (EventFlow::Ignore, scene.on_error(ctx, err)?)
+                }
+                Err(err) => return Err(err),
+            };
+            match flow {
+                EventFlow::Consume => outcome = EventOutcome::Consumed,
+                EventFlow::Continue => outcome = EventOutcome::Observed,
+                EventFlow::Ignore => {}
+            }
+            // Ahead of the transitions from the scenes above, see `SceneStack`
+            self.operations.insert(event_start, trans);
+            if flow == EventFlow::Consume {
+                break;
+            }
+            if !is_focused && (scene.is_modal() || !scene.passes_events_below(ctx)) {
                 break;
             }
         }
-        for op in self.operations.drain(..) {
-            op.apply_to(&mut self.scenes, &mut self.ctx)?;
+        Ok(outcome)
+    }
+
+    /// The index of the focused scene, if it should get keyboard and text events first.
+    fn focused_index(&self) -> Option<usize> {
+        let index = self.focus?.min(self.scenes.len().checked_sub(1)?);
+        if self.scenes[index + 1..].iter().any(|s| s.is_modal()) {
+            return None;
+        }
+        Some(index)
+    }
+}
+
+/// A [`SceneStack`] along with the context passed to its scenes. With the default
+/// [`SgeBackend`], this is driven through [`sge::Application`].
+///
+/// This derefs to its stack, so everything that doesn't need the context, like
+/// [`SceneStack::len`] or [`SceneStack::current`], can be called on it directly. Methods that
+/// do need the context are repeated here without the `ctx` parameter.
+pub struct SceneManager<C, E = BoxError, B: Backend = SgeBackend> {
+    stack: SceneStack<C, E, B>,
+    /// The context, passed to scenes each loop iteration
+    pub ctx: C,
+}
+
+impl<C, E: From<SceneError>, B: Backend> SceneManager<C, E, B> {
+    pub fn new(ctx: C, base_scene: BoxScene<C, E, B>) -> Self {
+        Self {
+            stack: SceneStack::new(base_scene),
+            ctx,
+        }
+    }
+
+    /// Like [`SceneManager::new`], but with room for `cap` queued transitions up front, so
+    /// frames where many scenes transition at once don't have to allocate.
+    pub fn with_operation_capacity(ctx: C, base_scene: BoxScene<C, E, B>, cap: usize) -> Self {
+        let mut manager = Self::new(ctx, base_scene);
+        manager.stack.operations.reserve(cap);
+        manager
+    }
+
+    /// Start building a manager, which makes sure it starts with at least one scene.
+    pub fn builder(ctx: C) -> SceneManagerBuilder<C, E, B> {
+        SceneManagerBuilder {
+            ctx,
+            scenes: Vec::new(),
+            fixed_timestep: None,
+            operation_capacity: 0,
+        }
+    }
+
+    /// The stack of scenes, along with the context, for calling [`SceneStack`] methods that
+    /// take one.
+    pub fn split(&mut self) -> (&mut SceneStack<C, E, B>, &mut C) {
+        (&mut self.stack, &mut self.ctx)
+    }
+
+    /// Swap in a new context, returning the old one, without touching the stack.
+    ///
+    /// The scenes on the stack may rely on what they found in the old context, so this is
+    /// usually best done once they've been cleared, E.G. with [`SceneManager::clear`].
+    pub fn set_context(&mut self, ctx: C) -> C {
+        std::mem::replace(&mut self.ctx, ctx)
+    }
+
+    /// See [`SceneStack::visible_depth`].
+    pub fn visible_depth(&self) -> usize {
+        self.stack.visible_depth(&self.ctx)
+    }
+
+    /// See [`SceneStack::snapshot`].
+    pub fn snapshot(&self) -> Vec<SceneInfo> {
+        self.stack.snapshot(&self.ctx)
+    }
+
+    /// See [`SceneStack::top_draws_next`].
+    pub fn top_draws_next(&self) -> bool {
+        self.stack.top_draws_next(&self.ctx)
+    }
+
+    pub fn apply(&mut self, trans: Transition<C, E, B>) -> Result<(), E> {
+        self.stack.apply(&mut self.ctx, trans)
+    }
+
+    /// See [`SceneStack::apply_reporting`].
+    pub fn apply_reporting(&mut self, trans: Transition<C, E, B>) -> Result<usize, E> {
+        self.stack.apply_reporting(&mut self.ctx, trans)
+    }
+
+    /// See [`SceneStack::apply_all`].
+    pub fn apply_all(
+        &mut self,
+        transitions: impl IntoIterator<Item = Transition<C, E, B>>,
+    ) -> Result<(), TransitionError<E>> {
+        self.stack.apply_all(&mut self.ctx, transitions)
+    }
+
+    /// See [`SceneStack::send_to`].
+    pub fn send_to<M: Any>(&mut self, depth: usize, msg: M) -> Result<bool, E> {
+        self.stack.send_to(&mut self.ctx, depth, msg)
+    }
+
+    /// See [`SceneStack::insert_at`].
+    pub fn insert_at(&mut self, depth: usize, scene: BoxScene<C, E, B>) -> Result<(), E> {
+        self.stack.insert_at(&mut self.ctx, depth, scene)
+    }
+
+    /// See [`SceneStack::push`].
+    pub fn push(&mut self, new: BoxScene<C, E, B>) -> Result<SceneHandle, E> {
+        self.stack.push(&mut self.ctx, new)
+    }
+
+    pub fn pop(&mut self) -> Result<Option<BoxScene<C, E, B>>, E> {
+        self.stack.pop(&mut self.ctx)
+    }
+
+    /// See [`SceneStack::step`].
+    pub fn step(&mut self, elapsed_time: f64) -> Result<bool, E> {
+        self.stack.step(&mut self.ctx, elapsed_time)
+    }
+
+    /// See [`SceneStack::flush_operations`].
+    pub fn flush_operations(&mut self) -> Result<(), TransitionError<E>> {
+        self.stack.flush_operations(&mut self.ctx)
+    }
+
+    /// See [`SceneStack::dispatch_event`].
+    pub fn dispatch_event(&mut self, event: &B::Event) -> Result<EventOutcome, E> {
+        self.stack.dispatch_event(&mut self.ctx, event)
+    }
+
+    /// See [`SceneStack::remove`].
+    pub fn remove(&mut self, handle: SceneHandle) -> Result<Option<BoxScene<C, E, B>>, E> {
+        self.stack.remove(&mut self.ctx, handle)
+    }
+
+    /// See [`SceneStack::clear`].
+    pub fn clear(&mut self) -> Result<(), E> {
+        self.stack.clear(&mut self.ctx)
+    }
+
+    /// See [`SceneStack::reset`]. The context is left untouched.
+    pub fn reset(&mut self) -> Result<(), E> {
+        self.stack.reset(&mut self.ctx)
+    }
+
+    /// See [`SceneStack::replace`].
+    pub fn replace(&mut self, new: BoxScene<C, E, B>) -> Result<BoxScene<C, E, B>, E> {
+        self.stack.replace(&mut self.ctx, new)
+    }
+
+    /// See [`SceneStack::replace_at`].
+    pub fn replace_at(
+        &mut self,
+        depth: usize,
+        new: BoxScene<C, E, B>,
+    ) -> Result<BoxScene<C, E, B>, E> {
+        self.stack.replace_at(&mut self.ctx, depth, new)
+    }
+
+    /// See [`SceneStack::replace_below_top`].
+    pub fn replace_below_top(&mut self, new: BoxScene<C, E, B>) -> Result<(), E> {
+        self.stack.replace_below_top(&mut self.ctx, new)
+    }
+}
+
+/// Shows the depth of the stack and the name of each scene on it, from the bottom up.
+impl<C, E, B: Backend> fmt::Debug for SceneStack<C, E, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<_> = self.scenes.iter().map(|s| s.name()).collect();
+        f.debug_struct("SceneStack")
+            .field("depth", &self.scenes.len())
+            .field("scenes", &names)
+            .field("operations", &self.operations)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C, E, B: Backend> fmt::Debug for SceneManager<C, E, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SceneManager")
+            .field("stack", &self.stack)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C, E, B: Backend> std::ops::Deref for SceneManager<C, E, B> {
+    type Target = SceneStack<C, E, B>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.stack
+    }
+}
+
+impl<C, E, B: Backend> std::ops::DerefMut for SceneManager<C, E, B> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.stack
+    }
+}
+
+/// Calls `on_leave` on every remaining scene, from top to bottom.
+///
+/// This is best-effort: errors can't be returned from `drop`, so they're ignored (or logged,
+/// with the `tracing` feature), and every scene gets its `on_leave` regardless.
+impl<C, E, B: Backend> Drop for SceneManager<C, E, B> {
+    fn drop(&mut self) {
+        while let Some(mut scene) = self.stack.scenes.pop() {
+            // Not `Entry::leave`, as there's nowhere to apply a follow-up transition
+            if scene.scene.leave(&mut self.ctx).is_err() {
+                trace!(
+                    scene = scene.name(),
+                    "on_leave failed while dropping the manager"
+                );
+            }
+        }
+    }
+}
+
+/// Takes the remaining scenes, from the bottom of the stack to the top.
+///
+/// This skips the `on_leave` that dropping the manager would call, so tearing the scenes down
+/// is up to the caller.
+impl<C, E, B: Backend> IntoIterator for SceneManager<C, E, B> {
+    type Item = BoxScene<C, E, B>;
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        // Leaves nothing for `drop` to call `on_leave` on
+        let scenes = std::mem::take(&mut self.stack.scenes);
+        scenes
+            .into_iter()
+            .map(|e| e.scene)
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+}
+
+/// Builds a [`SceneManager`], see [`SceneManager::builder`].
+pub struct SceneManagerBuilder<C, E = BoxError, B: Backend = SgeBackend> {
+    ctx: C,
+    scenes: Vec<BoxScene<C, E, B>>,
+    fixed_timestep: Option<f64>,
+    operation_capacity: usize,
+}
+
+impl<C, E: From<SceneError>, B: Backend> SceneManagerBuilder<C, E, B> {
+    /// Add a scene to the initial stack. Scenes are pushed in the order they're added, so the
+    /// last one ends up on top.
+    pub fn with_scene(mut self, scene: impl Scene<E, B, Context = C> + 'static) -> Self {
+        self.scenes.push(Box::new(scene));
+        self
+    }
+
+    /// See [`SceneStack::set_fixed_timestep`].
+    pub fn with_fixed_timestep(mut self, dt: f64) -> Self {
+        self.fixed_timestep = Some(dt);
+        self
+    }
+
+    /// See [`SceneManager::with_operation_capacity`].
+    pub fn with_operation_capacity(mut self, cap: usize) -> Self {
+        self.operation_capacity = cap;
+        self
+    }
+
+    /// Build the manager, entering each scene as it's pushed.
+    ///
+    /// Fails with [`SceneError::EmptyStack`] if no scene was added, as the manager would just
+    /// quit straight away.
+    pub fn build(self) -> Result<SceneManager<C, E, B>, E> {
+        if self.scenes.is_empty() {
+            return Err(SceneError::EmptyStack.into());
+        }
+        let mut manager = SceneManager {
+            stack: SceneStack::empty(),
+            ctx: self.ctx,
+        };
+        manager.set_fixed_timestep(self.fixed_timestep);
+        manager.stack.operations.reserve(self.operation_capacity);
+        manager.apply(Transition::PushAll(self.scenes))?;
+        Ok(manager)
+    }
+}
+
+impl<C, E> sge::Application for SceneManager<C, E, SgeBackend>
+where
+    E: From<SceneError>,
+    Box<dyn Error>: From<E>,
+{
+    fn on_create(&mut self) -> sge::ApplicationResult {
+        Ok(self.stack.on_create_borrowed(&mut self.ctx)?)
+    }
+
+    fn on_update(&mut self, elapsed_time: f64) -> sge::ApplicationResult {
+        Ok(self.stack.on_update_borrowed(&mut self.ctx, elapsed_time)?)
+    }
+
+    fn on_event(&mut self, event: &sge::Event) -> sge::ApplicationResult {
+        Ok(self.stack.on_event_borrowed(&mut self.ctx, event)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{run_frames, CallLog, MockScene};
+
+    #[derive(Debug, Clone, Copy)]
+    enum Op {
+        Push,
+        Pop,
+        Replace,
+        None,
+    }
+
+    /// A scene that returns `op` from its first update, replacing or pushing a scene called `new`.
+    fn scene(name: &str, log: &CallLog, op: Op, new: &str) -> MockScene<()> {
+        let scene = MockScene::new(name, log);
+        match op {
+            Op::Push => scene.with_transition(Transition::push(MockScene::new(new, log))),
+            Op::Pop => scene.with_transition(Transition::Pop),
+            Op::Replace => scene.with_transition(Transition::replace(MockScene::new(new, log))),
+            Op::None => scene,
+        }
+    }
+
+    fn names(manager: &SceneManager<()>) -> Vec<&str> {
+        manager.iter().map(|s| s.name()).collect()
+    }
+
+    /// Run one frame of `[base, below, top]`, with `top` drawing `below` so both are updated.
+    fn run(order: TransitionOrder, top: Op, below: Op) -> Vec<String> {
+        let log = CallLog::new();
+        let mut manager = SceneManager::new((), Box::new(MockScene::new("base", &log)));
+        manager.set_transition_order(order);
+        manager
+            .push(Box::new(scene("below", &log, below, "from_below")))
+            .unwrap();
+        manager
+            .push(Box::new(
+                scene("top", &log, top, "from_top").with_draw_next(true),
+            ))
+            .unwrap();
+        run_frames(&mut manager, 1, 0.1).unwrap();
+        manager.debug_assert_invariants();
+        names(&manager).into_iter().map(String::from).collect()
+    }
+
+    /// A scene that refuses to leave.
+    struct Stubborn;
+
+    impl Scene for Stubborn {
+        type Context = ();
+
+        fn on_update(
+            &mut self,
+            _ctx: &mut (),
+            _elapsed_time: f64,
+        ) -> Result<Transition<()>, BoxError> {
+            Ok(Transition::None)
+        }
+
+        fn on_before_leave(&mut self, _ctx: &mut ()) -> Result<bool, BoxError> {
+            Ok(false)
+        }
+
+        fn name(&self) -> &str {
+            "stubborn"
+        }
+    }
+
+    #[test]
+    fn remove_at_below_top_can_be_cancelled() {
+        let log = CallLog::new();
+        let mut manager = SceneManager::new((), Box::new(Stubborn));
+        manager.push(Box::new(MockScene::new("top", &log))).unwrap();
+        manager.apply(Transition::RemoveAt(0)).unwrap();
+        assert_eq!(names(&manager), ["stubborn", "top"]);
+    }
+
+    #[test]
+    fn conditions_are_observed_as_the_branch_taken() {
+        let log = CallLog::new();
+        let mut manager = SceneManager::new((), Box::new(MockScene::<()>::new("base", &log)));
+        let kinds = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let observed = kinds.clone();
+        manager.set_on_transition(Box::new(move |kind, _| observed.borrow_mut().push(*kind)));
+        run_frames(&mut manager, 1, 0.5).unwrap();
+
+        manager
+            .apply(Transition::when(
+                |_| true,
+                Transition::None,
+                Transition::Pop,
+            ))
+            .unwrap();
+        assert_eq!(manager.time_in_current_scene(), 0.5);
+        let next = Transition::push(MockScene::new("next", &log));
+        manager
+            .apply(Transition::when(|_| false, Transition::None, next))
+            .unwrap();
+        assert_eq!(*kinds.borrow(), [TransitionKind::Push]);
+    }
+
+    struct Panics;
+
+    impl Scene for Panics {
+        type Context = ();
+
+        fn on_update(
+            &mut self,
+            _ctx: &mut (),
+            _elapsed_time: f64,
+        ) -> Result<Transition<()>, BoxError> {
+            panic!("Scene failed");
+        }
+    }
+
+    #[test]
+    fn panic_drops_the_frames_transitions() {
+        let log = CallLog::new();
+        let mut manager = SceneManager::new((), Box::new(Panics));
+        manager.set_catch_unwind(true);
+        let next = Transition::push(MockScene::new("next", &log));
+        let top = MockScene::new("top", &log)
+            .with_draw_next(true)
+            .with_transition(next);
+        manager.push(Box::new(top)).unwrap();
+
+        let err = manager.step(0.1).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<SceneError>(),
+            Some(&SceneError::ScenePanicked { depth: 0 })
+        );
+        assert_eq!(manager.pending_operations(), 0);
+        assert_eq!(names(&manager), ["top"]);
+        manager.debug_assert_invariants();
+    }
+
+    /// A scene that records the opacity it's drawn at.
+    struct Fading {
+        name: &'static str,
+        alphas: std::rc::Rc<std::cell::RefCell<Vec<(&'static str, f64)>>>,
+    }
+
+    impl Scene for Fading {
+        type Context = ();
+
+        fn on_update(
+            &mut self,
+            _ctx: &mut (),
+            _elapsed_time: f64,
+        ) -> Result<Transition<()>, BoxError> {
+            Ok(Transition::None)
+        }
+
+        fn on_draw_with_alpha(&mut self, _ctx: &mut (), alpha: f64) -> Result<(), BoxError> {
+            self.alphas.borrow_mut().push((self.name, alpha));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn fade_draws_both_scenes_at_their_share_of_the_opacity() {
+        let alphas = std::rc::Rc::default();
+        let scene = |name| Fading {
+            name,
+            alphas: std::rc::Rc::clone(&alphas),
+        };
+        let mut manager = SceneManager::new((), Box::new(scene("old")));
+        manager.apply(Transition::fade(scene("new"), 1.0)).unwrap();
+
+        run_frames(&mut manager, 1, 0.25).unwrap();
+        assert_eq!(*alphas.borrow(), [("old", 0.75), ("new", 0.25)]);
+        alphas.borrow_mut().clear();
+        run_frames(&mut manager, 1, 1.0).unwrap();
+        assert_eq!(*alphas.borrow(), [("new", 1.0)]);
+    }
+
+    #[test]
+    fn replace_below_top_respects_the_max_depth() {
+        let log = CallLog::new();
+        let mut manager = SceneManager::new((), Box::new(MockScene::<()>::new("top", &log)));
+        manager.set_max_depth(Some(1));
+        let err = manager
+            .replace_below_top(Box::new(MockScene::new("below", &log)))
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<SceneError>(),
+            Some(&SceneError::DepthExceeded { max: 1 })
+        );
+        assert_eq!(names(&manager), ["top"]);
+        assert!(log.calls_to("below").is_empty());
+    }
+
+    /// A scene that pushes `next` as soon as it's entered.
+    struct Redirects(Option<MockScene<()>>);
+
+    impl Scene for Redirects {
+        type Context = ();
+
+        fn on_enter_transition(&mut self, _ctx: &mut ()) -> Result<Transition<()>, BoxError> {
+            Ok(self.0.take().map_or(Transition::None, Transition::push))
+        }
+
+        fn on_update(
+            &mut self,
+            _ctx: &mut (),
+            _elapsed_time: f64,
+        ) -> Result<Transition<()>, BoxError> {
+            Ok(Transition::None)
+        }
+
+        fn name(&self) -> &str {
+            "redirects"
+        }
+    }
+
+    #[test]
+    fn crossfaded_scene_gets_its_enter_transition() {
+        let log = CallLog::new();
+        let mut manager = SceneManager::new((), Box::new(MockScene::<()>::new("old", &log)));
+        let next = Redirects(Some(MockScene::new("next", &log)));
+        manager.apply(Transition::crossfade(next, 0.1)).unwrap();
+        run_frames(&mut manager, 2, 0.1).unwrap();
+        assert_eq!(names(&manager), ["redirects", "next"]);
+    }
+
+    /// A dialog that keeps the handle it's given, so the test can send messages through it.
+    struct Dialog(std::rc::Rc<std::cell::RefCell<Option<ParentRef>>>);
+
+    impl Scene for Dialog {
+        type Context = ();
+
+        fn on_parent(&mut self, parent: ParentRef) {
+            *self.0.borrow_mut() = Some(parent);
+        }
+
+        fn on_update(
+            &mut self,
+            _ctx: &mut (),
+            _elapsed_time: f64,
+        ) -> Result<Transition<()>, BoxError> {
+            Ok(Transition::None)
+        }
+    }
+
+    /// A scene that keeps the `&str` results it's unpaused with.
+    struct Opener(std::rc::Rc<std::cell::RefCell<Vec<&'static str>>>);
+
+    impl Scene for Opener {
+        type Context = ();
+
+        fn on_unpause(
+            &mut self,
+            _ctx: &mut (),
+            result: Option<Box<dyn Any>>,
+        ) -> Result<(), BoxError> {
+            if let Some(answer) = result.and_then(|r| r.downcast::<&str>().ok()) {
+                self.0.borrow_mut().push(*answer);
+            }
+            Ok(())
+        }
+
+        fn on_update(
+            &mut self,
+            _ctx: &mut (),
+            _elapsed_time: f64,
+        ) -> Result<Transition<()>, BoxError> {
+            Ok(Transition::None)
+        }
+    }
+
+    #[test]
+    fn message_to_parent_arrives_when_popped() {
+        let answers = std::rc::Rc::default();
+        let mut manager = SceneManager::new((), Box::new(Opener(std::rc::Rc::clone(&answers))));
+        let parent = std::rc::Rc::default();
+        manager
+            .push(Box::new(Dialog(std::rc::Rc::clone(&parent))))
+            .unwrap();
+        let parent = parent.borrow_mut().take().unwrap();
+        parent.send("first");
+        parent.send("second");
+        assert!(answers.borrow().is_empty());
+
+        manager.apply(Transition::Pop).unwrap();
+        assert_eq!(*answers.borrow(), ["second"]);
+        // Dropped rather than delivered to anyone, as the dialog has gone
+        parent.send("late");
+        manager
+            .push(Box::new(MockScene::new("next", &CallLog::new())))
+            .unwrap();
+        manager.apply(Transition::Pop).unwrap();
+        assert_eq!(*answers.borrow(), ["second"]);
+    }
+
+    /// A scene that keeps the seed it's given.
+    struct Seeded(std::rc::Rc<std::cell::Cell<Option<u64>>>);
+
+    impl Scene for Seeded {
+        type Context = ();
+
+        fn on_seed(&mut self, seed: u64) {
+            self.0.set(Some(seed));
+        }
+
+        fn on_update(
+            &mut self,
+            _ctx: &mut (),
+            _elapsed_time: f64,
+        ) -> Result<Transition<()>, BoxError> {
+            Ok(Transition::None)
+        }
+    }
+
+    #[test]
+    fn crossfaded_scene_is_seeded() {
+        let log = CallLog::new();
+        let mut manager = SceneManager::new((), Box::new(MockScene::<()>::new("old", &log)));
+        manager.set_seed(Some(1));
+        let seed = std::rc::Rc::default();
+        manager
+            .apply(Transition::crossfade(
+                Seeded(std::rc::Rc::clone(&seed)),
+                0.1,
+            ))
+            .unwrap();
+        assert!(seed.get().is_some());
+    }
+
+    /// A scene that pushes `next` once it's left.
+    struct PushesOnLeave(&'static str, Option<MockScene<()>>);
+
+    impl Scene for PushesOnLeave {
+        type Context = ();
+
+        fn on_leave_transition(&mut self, _ctx: &mut ()) -> Result<Transition<()>, BoxError> {
+            Ok(self.1.take().map_or(Transition::None, Transition::push))
+        }
+
+        fn on_update(
+            &mut self,
+            _ctx: &mut (),
+            _elapsed_time: f64,
+        ) -> Result<Transition<()>, BoxError> {
+            Ok(Transition::None)
+        }
+
+        fn name(&self) -> &str {
+            self.0
+        }
+    }
+
+    #[test]
+    fn crossfaded_out_scene_gets_its_leave_transition() {
+        let log = CallLog::new();
+        let old = PushesOnLeave("old", Some(MockScene::new("credits", &log)));
+        let mut manager = SceneManager::new((), Box::new(old));
+        manager
+            .apply(Transition::crossfade(MockScene::new("new", &log), 0.1))
+            .unwrap();
+        run_frames(&mut manager, 2, 0.1).unwrap();
+        assert_eq!(names(&manager), ["new", "credits"]);
+    }
+
+    #[test]
+    fn removing_a_crossfade_early_keeps_both_leave_transitions() {
+        let log = CallLog::new();
+        let mut manager = SceneManager::new((), Box::new(MockScene::<()>::new("base", &log)));
+        let old = PushesOnLeave("old", Some(MockScene::new("old credits", &log)));
+        let new = PushesOnLeave("new", Some(MockScene::new("new credits", &log)));
+        manager.push(Box::new(old)).unwrap();
+        manager.apply(Transition::crossfade(new, 1.0)).unwrap();
+        run_frames(&mut manager, 1, 0.1).unwrap();
+
+        manager.apply(Transition::Pop).unwrap();
+        assert_eq!(names(&manager), ["base", "old credits", "new credits"]);
+    }
+
+    /// A scene that follows up once it's left, without anything further coming of it.
+    struct FollowsUp;
+
+    impl Scene for FollowsUp {
+        type Context = ();
+
+        fn on_leave_transition(&mut self, _ctx: &mut ()) -> Result<Transition<()>, BoxError> {
+            Ok(Transition::Sequence(Vec::new()))
+        }
+
+        fn on_update(
+            &mut self,
+            _ctx: &mut (),
+            _elapsed_time: f64,
+        ) -> Result<Transition<()>, BoxError> {
+            Ok(Transition::None)
+        }
+    }
+
+    #[test]
+    fn follow_ups_side_by_side_dont_count_as_a_loop() {
+        let mut manager = SceneManager::new((), Box::new(FollowsUp));
+        for _ in 1..MAX_FOLLOW_UPS + 10 {
+            manager.push(Box::new(FollowsUp)).unwrap();
+        }
+        manager.apply(Transition::Clear).unwrap();
+        assert!(manager.is_empty());
+    }
+
+    /// A scene that replaces itself with another of itself as soon as it's entered.
+    struct Loops;
+
+    impl Scene for Loops {
+        type Context = ();
+
+        fn on_enter_transition(&mut self, _ctx: &mut ()) -> Result<Transition<()>, BoxError> {
+            Ok(Transition::replace(Loops))
+        }
+
+        fn on_update(
+            &mut self,
+            _ctx: &mut (),
+            _elapsed_time: f64,
+        ) -> Result<Transition<()>, BoxError> {
+            Ok(Transition::None)
+        }
+    }
+
+    #[test]
+    fn follow_ups_leading_to_each_other_are_a_loop() {
+        let mut manager = SceneManager::new((), Box::new(FollowsUp));
+        let err = manager.apply(Transition::push(Loops)).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<SceneError>(),
+            Some(&SceneError::RedirectLoop {
+                max: MAX_FOLLOW_UPS
+            })
+        );
+    }
+
+    #[test]
+    fn cancelled_transitions_arent_observed() {
+        let log = CallLog::new();
+        let mut manager = SceneManager::new((), Box::new(Stubborn));
+        let kinds = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let observed = kinds.clone();
+        manager.set_on_transition(Box::new(move |kind, _| observed.borrow_mut().push(*kind)));
+        manager.start_recording();
+        run_frames(&mut manager, 1, 0.5).unwrap();
+
+        manager
+            .apply(Transition::replace(MockScene::new("new", &log)))
+            .unwrap();
+        manager.apply(Transition::Pop).unwrap();
+        assert_eq!(names(&manager), ["stubborn"]);
+        assert!(kinds.borrow().is_empty());
+        assert!(manager.take_recording().is_empty());
+        assert_eq!(manager.time_in_current_scene(), 0.5);
+    }
+
+    #[test]
+    fn remove_at_out_of_range_does_nothing() {
+        let log = CallLog::new();
+        let mut manager = SceneManager::new((), Box::new(MockScene::<()>::new("base", &log)));
+        manager.apply(Transition::RemoveAt(usize::MAX)).unwrap();
+        assert_eq!(names(&manager), ["base"]);
+    }
+
+    #[test]
+    fn transitions_from_different_depths_apply_in_order() {
+        use TransitionOrder::*;
+        #[rustfmt::skip]
+        let cases: &[(Op, Op, TransitionOrder, &[&str])] = &[
+            (Op::Push, Op::Pop, AsCollected, &["base", "below", "from_top"]),
+            (Op::Push, Op::Pop, PopsFirst, &["base", "below", "from_top"]),
+            (Op::Push, Op::Pop, PushesFirst, &["base", "below", "top"]),
+            (Op::Push, Op::Push, AsCollected, &["base", "below", "top", "from_below", "from_top"]),
+            (Op::Push, Op::Push, PopsFirst, &["base", "below", "top", "from_below", "from_top"]),
+            (Op::Push, Op::Push, PushesFirst, &["base", "below", "top", "from_below", "from_top"]),
+            (Op::Push, Op::None, AsCollected, &["base", "below", "top", "from_top"]),
+            (Op::Push, Op::None, PopsFirst, &["base", "below", "top", "from_top"]),
+            (Op::Push, Op::None, PushesFirst, &["base", "below", "top", "from_top"]),
+            (Op::Pop, Op::Pop, AsCollected, &["base"]),
+            (Op::Pop, Op::Pop, PopsFirst, &["base"]),
+            (Op::Pop, Op::Pop, PushesFirst, &["base"]),
+            (Op::Pop, Op::Push, AsCollected, &["base", "below", "top"]),
+            (Op::Pop, Op::Push, PopsFirst, &["base", "below", "from_below"]),
+            (Op::Pop, Op::Push, PushesFirst, &["base", "below", "top"]),
+            (Op::Pop, Op::None, AsCollected, &["base", "below"]),
+            (Op::Pop, Op::None, PopsFirst, &["base", "below"]),
+            (Op::Pop, Op::None, PushesFirst, &["base", "below"]),
+            (Op::Replace, Op::Pop, AsCollected, &["base", "from_top"]),
+            (Op::Replace, Op::Pop, PopsFirst, &["base", "from_top"]),
+            (Op::Replace, Op::Pop, PushesFirst, &["base", "from_top"]),
+            (Op::Replace, Op::Push, AsCollected, &["base", "below", "top", "from_top"]),
+            (Op::Replace, Op::Push, PopsFirst, &["base", "below", "top", "from_top"]),
+            (Op::Replace, Op::Push, PushesFirst, &["base", "below", "top", "from_top"]),
+            (Op::Replace, Op::None, AsCollected, &["base", "below", "from_top"]),
+            (Op::Replace, Op::None, PopsFirst, &["base", "below", "from_top"]),
+            (Op::Replace, Op::None, PushesFirst, &["base", "below", "from_top"]),
+        ];
+        for &(top, below, order, expected) in cases {
+            assert_eq!(
+                run(order, top, below),
+                expected,
+                "{top:?} from the top and {below:?} from below, {order:?}"
+            );
         }
-        Ok(was_handled)
     }
 }
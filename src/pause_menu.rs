@@ -0,0 +1,240 @@
+use std::{any::Any, task::Poll};
+
+use crate::{Backend, CursorMode, EventFlow, EventMask, ParentRef, Scene, Transition};
+
+/// Wraps a menu scene so the scenes below it keep being drawn, but stop being updated and
+/// seeing events, see [`Transition::pause_menu`].
+///
+/// Everything is passed on to the menu, except that `draw_next` and `is_modal` always return
+/// `true`. To resume, the menu just pops itself, and the scene below gets `on_unpause` as usual.
+pub struct PauseMenu<S> {
+    menu: S,
+}
+
+impl<S> PauseMenu<S> {
+    pub fn new(menu: S) -> Self {
+        Self { menu }
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.menu
+    }
+
+    pub fn inner_mut(&mut self) -> &mut S {
+        &mut self.menu
+    }
+
+    pub fn into_inner(self) -> S {
+        self.menu
+    }
+}
+
+impl<S: Scene<E, B> + 'static, E, B: Backend> Scene<E, B> for PauseMenu<S> {
+    type Context = S::Context;
+
+    fn on_seed(&mut self, seed: u64) {
+        self.menu.on_seed(seed)
+    }
+    fn on_parent(&mut self, parent: ParentRef) {
+        self.menu.on_parent(parent)
+    }
+    fn on_enter(&mut self, ctx: &mut Self::Context) -> Result<(), E> {
+        self.menu.on_enter(ctx)
+    }
+    fn on_enter_with(&mut self, ctx: &mut Self::Context, payload: Box<dyn Any>) -> Result<(), E> {
+        self.menu.on_enter_with(ctx, payload)
+    }
+    fn on_enter_transition(
+        &mut self,
+        ctx: &mut Self::Context,
+    ) -> Result<Transition<Self::Context, E, B>, E> {
+        self.menu.on_enter_transition(ctx)
+    }
+    fn on_leave(&mut self, ctx: &mut Self::Context) -> Result<(), E> {
+        self.menu.on_leave(ctx)
+    }
+    fn on_leave_transition(
+        &mut self,
+        ctx: &mut Self::Context,
+    ) -> Result<Transition<Self::Context, E, B>, E> {
+        self.menu.on_leave_transition(ctx)
+    }
+    fn on_before_leave(&mut self, ctx: &mut Self::Context) -> Result<bool, E> {
+        self.menu.on_before_leave(ctx)
+    }
+    fn on_pause(&mut self, ctx: &mut Self::Context, should_draw: bool) -> Result<(), E> {
+        self.menu.on_pause(ctx, should_draw)
+    }
+    fn on_pause_with(
+        &mut self,
+        ctx: &mut Self::Context,
+        should_draw: bool,
+        reason: Option<&dyn Any>,
+    ) -> Result<(), E> {
+        self.menu.on_pause_with(ctx, should_draw, reason)
+    }
+    fn pause_reason(&self) -> Option<&dyn Any> {
+        self.menu.pause_reason()
+    }
+    fn on_unpause(
+        &mut self,
+        ctx: &mut Self::Context,
+        result: Option<Box<dyn Any>>,
+    ) -> Result<(), E> {
+        self.menu.on_unpause(ctx, result)
+    }
+    fn on_unpause_with(
+        &mut self,
+        ctx: &mut Self::Context,
+        result: Option<Box<dyn Any>>,
+        was_drawn: bool,
+    ) -> Result<(), E> {
+        self.menu.on_unpause_with(ctx, result, was_drawn)
+    }
+    fn result(&mut self) -> Option<Box<dyn Any>> {
+        self.menu.result()
+    }
+
+    fn on_create(&mut self, ctx: &mut Self::Context) -> Result<bool, E> {
+        self.menu.on_create(ctx)
+    }
+    fn on_update(
+        &mut self,
+        ctx: &mut Self::Context,
+        elapsed_time: f64,
+    ) -> Result<Transition<Self::Context, E, B>, E> {
+        self.menu.on_update(ctx, elapsed_time)
+    }
+    fn on_update_many(
+        &mut self,
+        ctx: &mut Self::Context,
+        elapsed_time: f64,
+        transitions: &mut Vec<Transition<Self::Context, E, B>>,
+    ) -> Result<(), E> {
+        self.menu.on_update_many(ctx, elapsed_time, transitions)
+    }
+    fn poll_ready(
+        &mut self,
+        ctx: &mut Self::Context,
+    ) -> Result<Poll<Transition<Self::Context, E, B>>, E> {
+        self.menu.poll_ready(ctx)
+    }
+    fn tick_interval(&self) -> Option<f64> {
+        self.menu.tick_interval()
+    }
+    fn on_tick(&mut self, ctx: &mut Self::Context) -> Result<(), E> {
+        self.menu.on_tick(ctx)
+    }
+    fn update_budget(&self) -> Option<f64> {
+        self.menu.update_budget()
+    }
+    fn wants_below(&self) -> bool {
+        self.menu.wants_below()
+    }
+    fn on_update_with_below(
+        &mut self,
+        ctx: &mut Self::Context,
+        elapsed_time: f64,
+        below: Option<&dyn Scene<E, B, Context = Self::Context>>,
+    ) -> Result<Transition<Self::Context, E, B>, E> {
+        self.menu.on_update_with_below(ctx, elapsed_time, below)
+    }
+    fn on_error(
+        &mut self,
+        ctx: &mut Self::Context,
+        err: E,
+    ) -> Result<Transition<Self::Context, E, B>, E> {
+        self.menu.on_error(ctx, err)
+    }
+    fn on_fixed_update(&mut self, ctx: &mut Self::Context, dt: f64) -> Result<(), E> {
+        self.menu.on_fixed_update(ctx, dt)
+    }
+
+    fn on_event(
+        &mut self,
+        ctx: &mut Self::Context,
+        event: &B::Event,
+    ) -> Result<(bool, Transition<Self::Context, E, B>), E> {
+        self.menu.on_event(ctx, event)
+    }
+    fn on_event_flow(
+        &mut self,
+        ctx: &mut Self::Context,
+        event: &B::Event,
+    ) -> Result<(EventFlow, Transition<Self::Context, E, B>), E> {
+        self.menu.on_event_flow(ctx, event)
+    }
+    fn event_mask(&self) -> EventMask {
+        self.menu.event_mask()
+    }
+    fn on_resize(&mut self, ctx: &mut Self::Context, width: u32, height: u32) -> Result<(), E> {
+        self.menu.on_resize(ctx, width, height)
+    }
+    fn on_background(&mut self, ctx: &mut Self::Context) -> Result<(), E> {
+        self.menu.on_background(ctx)
+    }
+    fn on_foreground(&mut self, ctx: &mut Self::Context) -> Result<(), E> {
+        self.menu.on_foreground(ctx)
+    }
+    fn on_message(&mut self, ctx: &mut Self::Context, msg: &dyn Any) -> Result<bool, E> {
+        self.menu.on_message(ctx, msg)
+    }
+
+    fn on_draw(&mut self, ctx: &mut Self::Context) -> Result<(), E> {
+        self.menu.on_draw(ctx)
+    }
+    fn on_draw_with_alpha(&mut self, ctx: &mut Self::Context, alpha: f64) -> Result<(), E> {
+        self.menu.on_draw_with_alpha(ctx, alpha)
+    }
+    /// Always `true`, so the game is drawn behind the menu.
+    fn draw_next(&self, _ctx: &Self::Context) -> bool {
+        true
+    }
+    /// Always `true`, so the game stops updating while the menu is open.
+    fn is_modal(&self) -> bool {
+        true
+    }
+    fn passes_events_below(&self, ctx: &Self::Context) -> bool {
+        self.menu.passes_events_below(ctx)
+    }
+    fn always_update(&self) -> bool {
+        self.menu.always_update()
+    }
+
+    fn cursor(&self) -> CursorMode {
+        self.menu.cursor()
+    }
+
+    fn name(&self) -> &str {
+        self.menu.name()
+    }
+    #[cfg(feature = "serde")]
+    fn save_state(&self) -> Option<serde_json::Value> {
+        self.menu.save_state()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        test_util::{run_frames, Call, CallLog, MockScene},
+        SceneManager, Transition,
+    };
+
+    #[test]
+    fn game_is_drawn_but_not_updated_under_the_menu() {
+        let log = CallLog::new();
+        let mut manager = SceneManager::new((), Box::new(MockScene::<()>::new("game", &log)));
+        manager
+            .apply(Transition::pause_menu(MockScene::new("menu", &log)))
+            .unwrap();
+        log.take();
+
+        run_frames(&mut manager, 1, 0.1).unwrap();
+        assert_eq!(log.calls_to("game"), [Call::Draw]);
+        assert_eq!(
+            log.calls_to("menu"),
+            [Call::Update { elapsed_time: 0.1 }, Call::Draw]
+        );
+    }
+}
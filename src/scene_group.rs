@@ -0,0 +1,367 @@
+use std::{any::Any, task::Poll};
+
+use crate::{
+    split_seed, Backend, BoxError, BoxScene, EventFlow, EventMask, ParentRef, Scene, SgeBackend,
+    Transition,
+};
+
+/// Several scenes sharing one level of the stack, E.G. the panels of a split-screen view, see
+/// [`Transition::push_group`].
+///
+/// Every member is entered, left, paused and unpaused along with the group, and gets every
+/// update, fixed update, window change and draw, in the order they were given. Events go to
+/// each member from the last to the first, stopping at one that consumes them. Transitions the
+/// members return are applied as though the group returned them, so a member popping removes
+/// the whole group. Pops from several members at once only remove the group once.
+///
+/// The group draws the scenes below it, is modal or always updates if any member does, and
+/// sees the events that any member does, though each member only gets those in its own
+/// [`Scene::event_mask`]. Members' `on_tick`, `on_enter_transition` and
+/// `on_leave_transition` aren't called.
+pub struct SceneGroup<C, E = BoxError, B: Backend = SgeBackend> {
+    scenes: Vec<BoxScene<C, E, B>>,
+}
+
+/// Drop every `Transition::Pop` from `start` on but the first, as each member popping means the
+/// same thing: remove the group.
+fn collapse_pops<C, E, B: Backend>(transitions: &mut Vec<Transition<C, E, B>>, start: usize) {
+    let mut tail = transitions.split_off(start);
+    let mut popped = false;
+    tail.retain(|t| !matches!(t, Transition::Pop) || !std::mem::replace(&mut popped, true));
+    transitions.append(&mut tail);
+}
+
+/// Combine the members' transitions into one, dropping every `Transition::None`, so the group
+/// only returns a transition when a member did.
+fn sequence<C, E, B: Backend>(mut transitions: Vec<Transition<C, E, B>>) -> Transition<C, E, B> {
+    transitions.retain(|t| !matches!(t, Transition::None));
+    if transitions.is_empty() {
+        Transition::None
+    } else {
+        Transition::Sequence(transitions)
+    }
+}
+
+impl<C, E, B: Backend> SceneGroup<C, E, B> {
+    pub fn new(scenes: Vec<BoxScene<C, E, B>>) -> Self {
+        Self { scenes }
+    }
+
+    pub fn scenes(&self) -> &[BoxScene<C, E, B>] {
+        &self.scenes
+    }
+
+    pub fn scenes_mut(&mut self) -> &mut [BoxScene<C, E, B>] {
+        &mut self.scenes
+    }
+}
+
+impl<C: 'static, E: 'static, B: Backend> Scene<E, B> for SceneGroup<C, E, B> {
+    type Context = C;
+
+    /// Gives each member a seed of its own, split off this one.
+    fn on_seed(&mut self, mut seed: u64) {
+        for scene in &mut self.scenes {
+            scene.on_seed(split_seed(&mut seed));
+        }
+    }
+
+    /// Gives each member the group's handle, so they share one message slot.
+    fn on_parent(&mut self, parent: ParentRef) {
+        for scene in &mut self.scenes {
+            scene.on_parent(parent.clone());
+        }
+    }
+
+    fn on_enter(&mut self, ctx: &mut C) -> Result<(), E> {
+        for scene in &mut self.scenes {
+            scene.enter(ctx, None)?;
+        }
+        Ok(())
+    }
+
+    /// Leaves the members in reverse, the way the stack leaves scenes.
+    fn on_leave(&mut self, ctx: &mut C) -> Result<(), E> {
+        for scene in self.scenes.iter_mut().rev() {
+            scene.leave(ctx)?;
+        }
+        Ok(())
+    }
+
+    /// Returns `false` if any member does.
+    fn on_before_leave(&mut self, ctx: &mut C) -> Result<bool, E> {
+        for scene in &mut self.scenes {
+            if !scene.before_leave(ctx)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    fn on_pause_with(
+        &mut self,
+        ctx: &mut C,
+        should_draw: bool,
+        reason: Option<&dyn Any>,
+    ) -> Result<(), E> {
+        for scene in &mut self.scenes {
+            scene.pause(ctx, should_draw, reason)?;
+        }
+        Ok(())
+    }
+
+    /// Only the last member gets the result, as it can't be shared.
+    fn on_unpause_with(
+        &mut self,
+        ctx: &mut C,
+        mut result: Option<Box<dyn Any>>,
+        was_drawn: bool,
+    ) -> Result<(), E> {
+        for scene in self.scenes.iter_mut().rev() {
+            scene.unpause(ctx, result.take(), was_drawn)?;
+        }
+        Ok(())
+    }
+
+    /// Returns `false` if any member does.
+    fn on_create(&mut self, ctx: &mut C) -> Result<bool, E> {
+        for scene in &mut self.scenes {
+            if !scene.on_create(ctx)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    fn on_update(&mut self, ctx: &mut C, elapsed_time: f64) -> Result<Transition<C, E, B>, E> {
+        let mut transitions = Vec::new();
+        self.on_update_many(ctx, elapsed_time, &mut transitions)?;
+        Ok(sequence(transitions))
+    }
+
+    fn on_update_many(
+        &mut self,
+        ctx: &mut C,
+        elapsed_time: f64,
+        transitions: &mut Vec<Transition<C, E, B>>,
+    ) -> Result<(), E> {
+        let start = transitions.len();
+        for scene in &mut self.scenes {
+            scene.on_update_many(ctx, elapsed_time, transitions)?;
+        }
+        collapse_pops(transitions, start);
+        Ok(())
+    }
+
+    /// Ready with the transitions of every member that is, once any of them are.
+    fn poll_ready(&mut self, ctx: &mut C) -> Result<Poll<Transition<C, E, B>>, E> {
+        let mut ready = Vec::new();
+        for scene in &mut self.scenes {
+            if let Poll::Ready(trans) = scene.poll_ready(ctx)? {
+                ready.push(trans);
+            }
+        }
+        collapse_pops(&mut ready, 0);
+        if ready.is_empty() {
+            Ok(Poll::Pending)
+        } else {
+            Ok(Poll::Ready(sequence(ready)))
+        }
+    }
+
+    fn on_fixed_update(&mut self, ctx: &mut C, dt: f64) -> Result<(), E> {
+        for scene in &mut self.scenes {
+            scene.on_fixed_update(ctx, dt)?;
+        }
+        Ok(())
+    }
+
+    fn on_event_flow(
+        &mut self,
+        ctx: &mut C,
+        event: &B::Event,
+    ) -> Result<(EventFlow, Transition<C, E, B>), E> {
+        let category = B::event_category(event);
+        let mut flow = EventFlow::Ignore;
+        let mut transitions = Vec::new();
+        // Skip members that don't want the event, as the stack does
+        for scene in self
+            .scenes
+            .iter_mut()
+            .rev()
+            .filter(|s| s.event_mask().intersects(category))
+        {
+            let (f, trans) = scene.on_event_flow(ctx, event)?;
+            transitions.push(trans);
+            match f {
+                EventFlow::Consume => {
+                    flow = f;
+                    break;
+                }
+                EventFlow::Continue => flow = f,
+                EventFlow::Ignore => {}
+            }
+        }
+        // Ahead of the transitions from the members after them, as on the stack
+        transitions.reverse();
+        collapse_pops(&mut transitions, 0);
+        Ok((flow, sequence(transitions)))
+    }
+
+    fn event_mask(&self) -> EventMask {
+        self.scenes
+            .iter()
+            .fold(EventMask::NONE, |mask, s| mask | s.event_mask())
+    }
+
+    fn on_resize(&mut self, ctx: &mut C, width: u32, height: u32) -> Result<(), E> {
+        for scene in &mut self.scenes {
+            scene.on_resize(ctx, width, height)?;
+        }
+        Ok(())
+    }
+
+    fn on_background(&mut self, ctx: &mut C) -> Result<(), E> {
+        for scene in &mut self.scenes {
+            scene.on_background(ctx)?;
+        }
+        Ok(())
+    }
+
+    fn on_foreground(&mut self, ctx: &mut C) -> Result<(), E> {
+        for scene in &mut self.scenes {
+            scene.on_foreground(ctx)?;
+        }
+        Ok(())
+    }
+
+    /// Offers the message to each member in turn, until one handles it.
+    fn on_message(&mut self, ctx: &mut C, msg: &dyn Any) -> Result<bool, E> {
+        for scene in &mut self.scenes {
+            if scene.on_message(ctx, msg)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn on_draw(&mut self, ctx: &mut C) -> Result<(), E> {
+        self.on_draw_with_alpha(ctx, 1.0)
+    }
+
+    fn on_draw_with_alpha(&mut self, ctx: &mut C, alpha: f64) -> Result<(), E> {
+        for scene in &mut self.scenes {
+            scene.on_draw_with_alpha(ctx, alpha)?;
+        }
+        Ok(())
+    }
+
+    fn draw_next(&self, ctx: &C) -> bool {
+        self.scenes.iter().any(|s| s.draw_next(ctx))
+    }
+
+    fn passes_events_below(&self, ctx: &C) -> bool {
+        self.scenes.iter().any(|s| s.passes_events_below(ctx))
+    }
+
+    fn is_modal(&self) -> bool {
+        self.scenes.iter().any(|s| s.is_modal())
+    }
+
+    fn always_update(&self) -> bool {
+        self.scenes.iter().any(|s| s.always_update())
+    }
+
+    fn name(&self) -> &str {
+        "group"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        test_util::{run_frames, send_event, Call, CallLog, MockScene},
+        Backend, BoxError, EventFlow, EventMask, SceneManager, Transition, WindowChange,
+    };
+
+    /// A backend whose events are just their categories.
+    struct Categories;
+
+    impl Backend for Categories {
+        type Event = EventMask;
+
+        fn window_change(_event: &EventMask) -> Option<WindowChange> {
+            None
+        }
+
+        fn event_category(event: &EventMask) -> EventMask {
+            *event
+        }
+    }
+
+    #[test]
+    fn members_popping_together_remove_the_group_once() {
+        let log = CallLog::new();
+        let mut manager = SceneManager::new((), Box::new(MockScene::<()>::new("base", &log)));
+        manager.push(Box::new(MockScene::new("mid", &log))).unwrap();
+        manager
+            .apply(Transition::push_group(vec![
+                Box::new(MockScene::new("a", &log).with_transition(Transition::Pop)),
+                Box::new(MockScene::new("b", &log).with_transition(Transition::Pop)),
+            ]))
+            .unwrap();
+
+        run_frames(&mut manager, 1, 0.1).unwrap();
+        let names: Vec<_> = manager.iter().map(|s| s.name()).collect();
+        assert_eq!(names, ["base", "mid"]);
+    }
+
+    #[test]
+    fn members_only_get_events_they_want() {
+        let log = CallLog::new();
+        let keys = MockScene::<(), BoxError, Categories>::new("keys", &log)
+            .with_event_mask(EventMask::KEYBOARD)
+            .with_event_flow(EventFlow::Consume);
+        let mouse = MockScene::new("mouse", &log).with_event_mask(EventMask::MOUSE);
+        let mut manager = SceneManager::new(
+            (),
+            Box::new(MockScene::new("base", &log).with_event_mask(EventMask::NONE)),
+        );
+        manager
+            .apply(Transition::push_group(vec![
+                Box::new(keys),
+                Box::new(mouse),
+            ]))
+            .unwrap();
+        log.take();
+
+        assert!(send_event(&mut manager, &EventMask::KEYBOARD).unwrap());
+        assert_eq!(log.take(), [("keys".to_string(), Call::Event)]);
+        assert!(!send_event(&mut manager, &EventMask::MOUSE).unwrap());
+        assert_eq!(log.take(), [("mouse".to_string(), Call::Event)]);
+    }
+
+    #[test]
+    fn ignored_events_leave_the_stack_alone() {
+        let log = CallLog::new();
+        let base = MockScene::<(), BoxError, Categories>::new("base", &log);
+        let mut manager = SceneManager::new((), Box::new(base));
+        manager
+            .apply(Transition::push_group(vec![
+                Box::new(MockScene::new("a", &log)),
+                Box::new(MockScene::new("b", &log)),
+            ]))
+            .unwrap();
+        let kinds = Rc::new(RefCell::new(Vec::new()));
+        let observed = kinds.clone();
+        manager.set_on_transition(Box::new(move |kind, _| observed.borrow_mut().push(*kind)));
+        run_frames(&mut manager, 1, 0.5).unwrap();
+
+        send_event(&mut manager, &EventMask::KEYBOARD).unwrap();
+        run_frames(&mut manager, 1, 0.5).unwrap();
+        assert!(kinds.borrow().is_empty());
+        assert_eq!(manager.time_in_current_scene(), 1.0);
+    }
+}
@@ -0,0 +1,299 @@
+//! Helpers for testing scenes and transitions without a running `sge` application.
+//!
+//! [`MockScene`] records every callback it gets into a shared [`CallLog`], so tests can push a
+//! few of them onto a manager, drive it with [`run_frames`] and [`send_event`], and check the
+//! order things happened in.
+
+use std::{any::Any, cell::RefCell, collections::VecDeque, marker::PhantomData, rc::Rc};
+
+use crate::{
+    Backend, BoxError, EventFlow, EventMask, Scene, SceneError, SceneManager, SgeBackend,
+    Transition,
+};
+
+/// A callback that a [`MockScene`] received.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Call {
+    Enter,
+    /// Entered through [`Transition::PushWith`]
+    EnterWith,
+    Leave,
+    Pause {
+        should_draw: bool,
+    },
+    Unpause {
+        has_result: bool,
+    },
+    Create,
+    Update {
+        elapsed_time: f64,
+    },
+    FixedUpdate {
+        dt: f64,
+    },
+    Event,
+    Resize {
+        width: u32,
+        height: u32,
+    },
+    Background,
+    Foreground,
+    Message,
+    Draw,
+}
+
+/// The calls made to one or more [`MockScene`]s, each with the name of the scene it was made
+/// on. Clones share the same log.
+#[derive(Debug, Clone, Default)]
+pub struct CallLog(Rc<RefCell<Vec<(String, Call)>>>);
+
+impl CallLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn record(&self, name: &str, call: Call) {
+        self.0.borrow_mut().push((name.to_owned(), call));
+    }
+
+    /// Every call recorded so far, oldest first.
+    pub fn calls(&self) -> Vec<(String, Call)> {
+        self.0.borrow().clone()
+    }
+
+    /// The calls recorded so far, leaving the log empty.
+    pub fn take(&self) -> Vec<(String, Call)> {
+        std::mem::take(&mut self.0.borrow_mut())
+    }
+
+    /// The calls made on the scene called `name`, oldest first.
+    pub fn calls_to(&self, name: &str) -> Vec<Call> {
+        self.0
+            .borrow()
+            .iter()
+            .filter(|(n, _)| n == name)
+            .map(|(_, c)| c.clone())
+            .collect()
+    }
+}
+
+/// A scene that records its callbacks in a [`CallLog`], and otherwise does what it's told.
+///
+/// Each update returns the next transition queued with [`MockScene::with_transition`], or
+/// [`Transition::None`] once they've run out.
+pub struct MockScene<C, E = BoxError, B: Backend = SgeBackend> {
+    name: String,
+    log: CallLog,
+    transitions: VecDeque<Transition<C, E, B>>,
+    event_flow: EventFlow,
+    event_mask: EventMask,
+    draw_next: bool,
+    modal: bool,
+    _ctx: PhantomData<fn(&mut C)>,
+}
+
+impl<C, E, B: Backend> MockScene<C, E, B> {
+    pub fn new(name: impl Into<String>, log: &CallLog) -> Self {
+        Self {
+            name: name.into(),
+            log: log.clone(),
+            transitions: VecDeque::new(),
+            event_flow: EventFlow::Ignore,
+            event_mask: EventMask::ALL,
+            draw_next: false,
+            modal: false,
+            _ctx: PhantomData,
+        }
+    }
+
+    /// Queue a transition to return from an update, after any queued before it.
+    pub fn with_transition(mut self, trans: Transition<C, E, B>) -> Self {
+        self.transitions.push_back(trans);
+        self
+    }
+
+    /// What to do with every event, [`EventFlow::Ignore`] by default.
+    pub fn with_event_flow(mut self, flow: EventFlow) -> Self {
+        self.event_flow = flow;
+        self
+    }
+
+    /// Which events to get, [`EventMask::ALL`] by default.
+    pub fn with_event_mask(mut self, mask: EventMask) -> Self {
+        self.event_mask = mask;
+        self
+    }
+
+    pub fn with_draw_next(mut self, draw_next: bool) -> Self {
+        self.draw_next = draw_next;
+        self
+    }
+
+    pub fn with_modal(mut self, modal: bool) -> Self {
+        self.modal = modal;
+        self
+    }
+
+    fn record(&self, call: Call) {
+        self.log.record(&self.name, call);
+    }
+}
+
+impl<C: 'static, E: 'static, B: Backend> Scene<E, B> for MockScene<C, E, B> {
+    type Context = C;
+
+    fn on_enter(&mut self, _ctx: &mut C) -> Result<(), E> {
+        self.record(Call::Enter);
+        Ok(())
+    }
+
+    fn on_enter_with(&mut self, _ctx: &mut C, _payload: Box<dyn Any>) -> Result<(), E> {
+        self.record(Call::EnterWith);
+        Ok(())
+    }
+
+    fn on_leave(&mut self, _ctx: &mut C) -> Result<(), E> {
+        self.record(Call::Leave);
+        Ok(())
+    }
+
+    fn on_pause(&mut self, _ctx: &mut C, should_draw: bool) -> Result<(), E> {
+        self.record(Call::Pause { should_draw });
+        Ok(())
+    }
+
+    fn on_unpause(&mut self, _ctx: &mut C, result: Option<Box<dyn Any>>) -> Result<(), E> {
+        self.record(Call::Unpause {
+            has_result: result.is_some(),
+        });
+        Ok(())
+    }
+
+    fn on_create(&mut self, _ctx: &mut C) -> Result<bool, E> {
+        self.record(Call::Create);
+        Ok(true)
+    }
+
+    fn on_update(&mut self, _ctx: &mut C, elapsed_time: f64) -> Result<Transition<C, E, B>, E> {
+        self.record(Call::Update { elapsed_time });
+        Ok(self.transitions.pop_front().unwrap_or_default())
+    }
+
+    fn on_fixed_update(&mut self, _ctx: &mut C, dt: f64) -> Result<(), E> {
+        self.record(Call::FixedUpdate { dt });
+        Ok(())
+    }
+
+    fn on_event_flow(
+        &mut self,
+        _ctx: &mut C,
+        _event: &B::Event,
+    ) -> Result<(EventFlow, Transition<C, E, B>), E> {
+        self.record(Call::Event);
+        Ok((self.event_flow, Transition::None))
+    }
+
+    fn event_mask(&self) -> EventMask {
+        self.event_mask
+    }
+
+    fn on_resize(&mut self, _ctx: &mut C, width: u32, height: u32) -> Result<(), E> {
+        self.record(Call::Resize { width, height });
+        Ok(())
+    }
+
+    fn on_background(&mut self, _ctx: &mut C) -> Result<(), E> {
+        self.record(Call::Background);
+        Ok(())
+    }
+
+    fn on_foreground(&mut self, _ctx: &mut C) -> Result<(), E> {
+        self.record(Call::Foreground);
+        Ok(())
+    }
+
+    fn on_message(&mut self, _ctx: &mut C, _msg: &dyn Any) -> Result<bool, E> {
+        self.record(Call::Message);
+        Ok(false)
+    }
+
+    fn on_draw(&mut self, _ctx: &mut C) -> Result<(), E> {
+        self.record(Call::Draw);
+        Ok(())
+    }
+
+    fn draw_next(&self, _ctx: &C) -> bool {
+        self.draw_next
+    }
+
+    fn is_modal(&self) -> bool {
+        self.modal
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Run `frames` updates of `elapsed_time` each, just as `sge` would, stopping early if the
+/// stack empties. Returns whether the manager is still running.
+pub fn run_frames<C, E: From<SceneError>, B: Backend>(
+    manager: &mut SceneManager<C, E, B>,
+    frames: usize,
+    elapsed_time: f64,
+) -> Result<bool, E> {
+    let (stack, ctx) = manager.split();
+    for _ in 0..frames {
+        if !stack.on_update_borrowed(ctx, elapsed_time)? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Dispatch a synthetic event, just as `sge` would, returning whether any scene handled it.
+pub fn send_event<C, E: From<SceneError>, B: Backend>(
+    manager: &mut SceneManager<C, E, B>,
+    event: &B::Event,
+) -> Result<bool, E> {
+    let (stack, ctx) = manager.split();
+    stack.on_event_borrowed(ctx, event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_pauses_and_pop_unpauses() {
+        let log = CallLog::new();
+        let mut manager = SceneManager::new((), Box::new(MockScene::<()>::new("a", &log)));
+        manager
+            .apply(Transition::push(
+                MockScene::new("b", &log)
+                    .with_draw_next(true)
+                    .with_transition(Transition::Pop),
+            ))
+            .unwrap();
+        assert_eq!(
+            log.take(),
+            [
+                ("a".to_owned(), Call::Pause { should_draw: true }),
+                ("b".to_owned(), Call::Enter),
+            ]
+        );
+
+        assert!(run_frames(&mut manager, 1, 0.5).unwrap());
+        assert_eq!(
+            log.take(),
+            [
+                ("b".to_owned(), Call::Update { elapsed_time: 0.5 }),
+                ("a".to_owned(), Call::Update { elapsed_time: 0.5 }),
+                ("b".to_owned(), Call::Leave),
+                ("a".to_owned(), Call::Unpause { has_result: false }),
+                ("a".to_owned(), Call::Draw),
+            ]
+        );
+    }
+}
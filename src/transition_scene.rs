@@ -0,0 +1,185 @@
+use crate::{Backend, BoxError, BoxScene, ParentRef, Scene, SceneError, SgeBackend, Transition};
+
+type Blend<C> = Box<dyn FnMut(&mut C, f64)>;
+
+/// Fades from one scene to another over a set time, see [`Transition::crossfade`].
+///
+/// This takes the place of the outgoing scene on the stack. The incoming scene is entered on
+/// the first update, then both are drawn, outgoing first, until the time is up. Neither is
+/// updated or sees events in the meantime. Then the outgoing scene is left, and the incoming
+/// one takes this scene's place, without being entered again. The outgoing scene's
+/// [`Scene::on_leave_transition`] and the incoming one's [`Scene::on_enter_transition`] are
+/// applied straight after, in that order, along with the rest of that frame's transitions. If
+/// this is removed before the fade is done, the `on_leave_transition`s of the scenes it leaves
+/// become its own.
+///
+/// Each scene is drawn with [`Scene::on_draw_with_alpha`], the outgoing one fading from 1 to 0
+/// and the incoming one from 0 to 1, so scenes that handle opacity themselves fade without any
+/// blend callback.
+pub struct TransitionScene<C, E = BoxError, B: Backend = SgeBackend> {
+    outgoing: Option<BoxScene<C, E, B>>,
+    incoming: Option<BoxScene<C, E, B>>,
+    entered: bool,
+    duration: f64,
+    elapsed: f64,
+    easing: fn(f64) -> f64,
+    blend: Option<Blend<C>>,
+    /// The `on_leave_transition`s of the scenes left in `on_leave`
+    after_leave: Vec<Transition<C, E, B>>,
+}
+
+impl<C, E, B: Backend> TransitionScene<C, E, B> {
+    /// Fade from `outgoing` to `incoming` over `duration`, in the same units as `elapsed_time`.
+    pub fn new(outgoing: BoxScene<C, E, B>, incoming: BoxScene<C, E, B>, duration: f64) -> Self {
+        Self {
+            outgoing: Some(outgoing),
+            incoming: Some(incoming),
+            entered: false,
+            duration,
+            elapsed: 0.0,
+            easing: |t| t,
+            blend: None,
+            after_leave: Vec::new(),
+        }
+    }
+
+    /// Shape the fade, by mapping the linear progress from 0 to 1 onto the blend factor. The
+    /// fade is linear by default.
+    pub fn with_easing(mut self, easing: fn(f64) -> f64) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Set a callback that's run before each scene is drawn, with the opacity it should be
+    /// drawn at, E.G. to set the renderer's alpha. Without one, both scenes are just drawn on
+    /// top of each other.
+    pub fn with_blend(mut self, blend: impl FnMut(&mut C, f64) + 'static) -> Self {
+        self.blend = Some(Box::new(blend));
+        self
+    }
+
+    /// How far through the fade this is, from 0 to 1, after easing.
+    pub fn progress(&self) -> f64 {
+        let t = if self.duration > 0.0 {
+            (self.elapsed / self.duration).min(1.0)
+        } else {
+            1.0
+        };
+        (self.easing)(t)
+    }
+}
+
+impl<C: 'static, E: From<SceneError> + 'static, B: Backend> Scene<E, B>
+    for TransitionScene<C, E, B>
+{
+    type Context = C;
+
+    /// Passes the seed on to the incoming scene, which is seeded when this is pushed or mapped
+    /// in by a [`Transition::Crossfade`], as it's entered later.
+    fn on_seed(&mut self, seed: u64) {
+        if let Some(incoming) = &mut self.incoming {
+            incoming.on_seed(seed);
+        }
+    }
+
+    /// Passes the handle on to the incoming scene, which keeps this scene's place once the fade
+    /// is done.
+    fn on_parent(&mut self, parent: ParentRef) {
+        if let Some(incoming) = &mut self.incoming {
+            incoming.on_parent(parent);
+        }
+    }
+
+    fn on_update(&mut self, ctx: &mut C, elapsed_time: f64) -> Result<Transition<C, E, B>, E> {
+        if !self.entered {
+            self.entered = true;
+            if let Some(incoming) = &mut self.incoming {
+                incoming.enter(ctx, None)?;
+            }
+            return Ok(Transition::None);
+        }
+        self.elapsed += elapsed_time;
+        if self.elapsed < self.duration {
+            return Ok(Transition::None);
+        }
+        // The stack only asks the scenes it enters and leaves itself
+        let left = match self.outgoing.take() {
+            Some(mut outgoing) => {
+                outgoing.leave(ctx)?;
+                outgoing.on_leave_transition(ctx)?
+            }
+            None => Transition::None,
+        };
+        match self.incoming.take() {
+            Some(mut incoming) => {
+                let then = incoming.on_enter_transition(ctx)?;
+                Ok(Transition::Sequence(vec![
+                    Transition::map(move |_| incoming),
+                    left,
+                    then,
+                ]))
+            }
+            None => Ok(left),
+        }
+    }
+
+    /// Leaves whichever scenes are still around, if this is removed before the fade is done.
+    fn on_leave(&mut self, ctx: &mut C) -> Result<(), E> {
+        if let Some(mut outgoing) = self.outgoing.take() {
+            outgoing.leave(ctx)?;
+            self.after_leave.push(outgoing.on_leave_transition(ctx)?);
+        }
+        if let Some(mut incoming) = self.incoming.take() {
+            if self.entered {
+                incoming.leave(ctx)?;
+                self.after_leave.push(incoming.on_leave_transition(ctx)?);
+            }
+        }
+        Ok(())
+    }
+
+    /// The `on_leave_transition`s of the scenes `on_leave` left, in the order they left.
+    fn on_leave_transition(&mut self, _ctx: &mut C) -> Result<Transition<C, E, B>, E> {
+        let mut after_leave = std::mem::take(&mut self.after_leave);
+        after_leave.retain(|t| !matches!(t, Transition::None));
+        if after_leave.is_empty() {
+            Ok(Transition::None)
+        } else {
+            Ok(Transition::Sequence(after_leave))
+        }
+    }
+
+    fn on_draw(&mut self, ctx: &mut C) -> Result<(), E> {
+        self.on_draw_with_alpha(ctx, 1.0)
+    }
+
+    /// Draws both scenes at their share of `alpha`, so fades nest.
+    fn on_draw_with_alpha(&mut self, ctx: &mut C, alpha: f64) -> Result<(), E> {
+        let t = self.progress();
+        if let Some(outgoing) = &mut self.outgoing {
+            let a = alpha * (1.0 - t);
+            if let Some(blend) = &mut self.blend {
+                blend(ctx, a);
+            }
+            outgoing.on_draw_with_alpha(ctx, a)?;
+        }
+        if let Some(incoming) = &mut self.incoming {
+            let a = alpha * t;
+            if let Some(blend) = &mut self.blend {
+                blend(ctx, a);
+            }
+            incoming.on_draw_with_alpha(ctx, a)?;
+        }
+        Ok(())
+    }
+
+    /// Draws the scenes below if either scene would.
+    fn draw_next(&self, ctx: &C) -> bool {
+        self.outgoing.iter().any(|s| s.draw_next(ctx))
+            || self.incoming.iter().any(|s| s.draw_next(ctx))
+    }
+
+    fn name(&self) -> &str {
+        "transition"
+    }
+}